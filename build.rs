@@ -0,0 +1,5 @@
+fn main() {
+    // The example tests are generated from the files in res/examples, so adding one
+    // has to trigger a rebuild
+    println!("cargo:rerun-if-changed=res/examples");
+}
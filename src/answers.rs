@@ -0,0 +1,184 @@
+//! Known-correct answers, read from a small TOML subset:
+//!
+//! ```toml
+//! [[answer]]
+//! day = 1
+//! part = 2
+//! value = 54530
+//! ```
+
+use std::{collections::BTreeMap, fmt::Display, path::Path};
+
+use itertools::Itertools;
+
+use crate::{toml, Answer, AocError, ParseError, Part};
+
+pub const DEFAULT_PATH: &str = "res/answers.toml";
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Answers(BTreeMap<(u8, Part), Answer>);
+
+impl Answers {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, AocError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|e| AocError::open(path, e))?;
+        Self::parse(&text).map_err(|error| AocError::Config {
+            path: path.to_owned(),
+            error,
+        })
+    }
+
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        let mut answers = BTreeMap::new();
+
+        for table in toml::tables(text, "answer")? {
+            let (mut day, mut part, mut value) = (None, None, None);
+            for field in &table.fields {
+                match field.key {
+                    "day" => day = Some(field.number()?),
+                    "part" => part = Some(field.part()?),
+                    "value" => value = Some(field.number()?),
+                    _ => return Err(field.unknown("day, part or value")),
+                }
+            }
+
+            let day = day.ok_or_else(|| table.missing("day"))?;
+            let part = part.ok_or_else(|| table.missing("part"))?;
+            let value = value.ok_or_else(|| table.missing("value"))?;
+            answers.insert((day, part), value);
+        }
+
+        Ok(Self(answers))
+    }
+
+    #[must_use]
+    pub fn get(&self, day: u8, part: Part) -> Option<Answer> {
+        self.0.get(&(day, part)).copied()
+    }
+
+    /// The days with at least one answer, in order and without repeats.
+    pub fn days(&self) -> impl Iterator<Item = u8> + '_ {
+        self.0.keys().map(|&(day, _)| day).dedup()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn insert(&mut self, day: u8, part: Part, answer: Answer) {
+        self.0.insert((day, part), answer);
+    }
+
+    /// Adds the answers from `other` for the parts this has none for.
+    pub fn fill_from(&mut self, other: Self) {
+        for (key, answer) in other.0 {
+            self.0.entry(key).or_insert(answer);
+        }
+    }
+
+    #[must_use]
+    pub fn check(&self, day: u8, part: Part, actual: Answer) -> Status {
+        match self.get(day, part) {
+            None => Status::Unknown,
+            Some(expected) if expected.as_i128() == actual.as_i128() => Status::Pass,
+            Some(_) => Status::Fail,
+        }
+    }
+}
+
+/// Serialises back into the format [`Answers::parse`] reads.
+impl Display for Answers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (idx, ((day, part), value)) in self.0.iter().enumerate() {
+            if idx > 0 {
+                writeln!(f)?;
+            }
+            writeln!(f, "[[answer]]")?;
+            writeln!(f, "day = {day}")?;
+            writeln!(f, "part = {}", part.number())?;
+            writeln!(f, "value = {value}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The result of comparing an answer against the store.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail,
+    Unknown,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Pass => write!(f, "pass"),
+            Self::Fail => write!(f, "FAIL"),
+            Self::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Answers, Status};
+    use crate::{Answer, ParseError, Part};
+
+    const ANSWERS: &str = "\
+# Checked against the puzzle site
+[[answer]]
+day = 1
+part = 1
+value = 142
+
+[[answer]]
+day = 9
+part = 2
+value = -3 # negative answers are fine
+";
+
+    #[test]
+    fn test_parse_round_trip() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+
+        assert_eq!(Some(Answer::Unsigned(142)), answers.get(1, Part::First));
+        assert_eq!(Some(Answer::Signed(-3)), answers.get(9, Part::Second));
+        assert_eq!(None, answers.get(1, Part::Second));
+
+        assert_eq!(answers, Answers::parse(&answers.to_string()).unwrap());
+    }
+
+    #[test]
+    fn test_check() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+
+        assert_eq!(Status::Pass, answers.check(1, Part::First, 142_u32.into()));
+        assert_eq!(
+            Status::Fail,
+            answers.check(1, Part::First, 143_usize.into())
+        );
+        assert_eq!(
+            Status::Pass,
+            answers.check(9, Part::Second, (-3_i64).into())
+        );
+        assert_eq!(Status::Unknown, answers.check(2, Part::First, 8_u64.into()));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Err(ParseError::new(1, 1, "\"[[answer]]\"")),
+            Answers::parse("day = 1")
+        );
+        assert_eq!(
+            Err(ParseError::new(3, 8, "1 or 2")),
+            Answers::parse("[[answer]]\nday = 1\npart = 3")
+        );
+        assert_eq!(
+            Err(ParseError::new(1, 1, "a value key")),
+            Answers::parse("[[answer]]\nday = 1\npart = 1")
+        );
+    }
+}
@@ -0,0 +1,273 @@
+//! Repeated timing of each day's parse and solve phases, plus a plain-text baseline
+//! format for spotting regressions between runs:
+//!
+//! ```text
+//! # day phase min_ns median_ns mean_ns
+//! 5 parse 81250 83000 85210
+//! 5 part2 912000 925000 931400
+//! ```
+
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    hint::black_box,
+    panic::{catch_unwind, AssertUnwindSafe},
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use crate::{parsers, registry, runner::panic_message, AocError, InputSource, ParseError, Part};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Phase {
+    Parse,
+    Solve(Part),
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Parse => write!(f, "parse"),
+            Self::Solve(part) => write!(f, "part{}", part.number()),
+        }
+    }
+}
+
+impl std::str::FromStr for Phase {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Self::Parse),
+            "part1" => Ok(Self::Solve(Part::First)),
+            "part2" => Ok(Self::Solve(Part::Second)),
+            x => Err(format!("{x} is not a valid phase")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+}
+
+impl Stats {
+    /// Summarises a non-empty set of samples.
+    #[must_use]
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty(), "no samples to summarise");
+        samples.sort_unstable();
+
+        let mid = samples.len() / 2;
+        let median = if samples.len() % 2 == 0 {
+            (samples[mid - 1] + samples[mid]) / 2
+        } else {
+            samples[mid]
+        };
+        let total = samples.iter().sum::<Duration>();
+        let count = u32::try_from(samples.len()).expect("sample count fits in u32");
+
+        Self {
+            min: samples[0],
+            median,
+            mean: total / count,
+        }
+    }
+
+    /// Relative change of the median against `baseline`, as a percentage.
+    #[must_use]
+    pub fn change(&self, baseline: &Self) -> f64 {
+        let baseline = baseline.median.as_secs_f64();
+        if baseline == 0.0 {
+            return 0.0;
+        }
+        (self.median.as_secs_f64() / baseline - 1.0) * 100.0
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Measurement {
+    pub day: u8,
+    pub phase: Phase,
+    pub stats: Stats,
+}
+
+/// Times `Solution::try_new` and both parts of a day `iterations` times each. The
+/// input is read once up front so file I/O is not part of the parse timing.
+pub fn bench_day(
+    number: u8,
+    input: &InputSource,
+    iterations: usize,
+) -> Result<Vec<Measurement>, String> {
+    let day = registry::find(number)?;
+    let lines = input.lines(number).map_err(|e| e.to_string())?;
+
+    let mut samples: BTreeMap<Phase, Vec<Duration>> = BTreeMap::new();
+
+    catch_unwind(AssertUnwindSafe(|| {
+        for _ in 0..iterations.max(1) {
+            let data = lines.clone();
+
+            let start = Instant::now();
+            let solver = day.build(data).map_err(|e| AocError::from(e).to_string())?;
+            samples
+                .entry(Phase::Parse)
+                .or_default()
+                .push(start.elapsed());
+
+            for part in Part::BOTH {
+                let start = Instant::now();
+                black_box(solver.solve(part));
+                samples
+                    .entry(Phase::Solve(part))
+                    .or_default()
+                    .push(start.elapsed());
+            }
+        }
+        Ok::<_, String>(())
+    }))
+    .map_err(|payload| panic_message(payload.as_ref()))??;
+
+    Ok(samples
+        .into_iter()
+        .map(|(phase, samples)| Measurement {
+            day: number,
+            phase,
+            stats: Stats::from_samples(samples),
+        })
+        .collect())
+}
+
+/// Previously saved timings, keyed by day and phase.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Baseline(BTreeMap<(u8, Phase), Stats>);
+
+impl Baseline {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, AocError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|e| AocError::open(path, e))?;
+        Self::parse(&text).map_err(|error| AocError::Config {
+            path: path.to_owned(),
+            error,
+        })
+    }
+
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        let mut baseline = BTreeMap::new();
+
+        for (line_idx, line) in text.lines().enumerate() {
+            if line.trim().is_empty() || line.trim_start().starts_with('#') {
+                continue;
+            }
+
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            let [day, phase, min, median, mean] = fields[..] else {
+                return Err(ParseError::at(
+                    line_idx,
+                    line,
+                    line.trim_start(),
+                    "day, phase, min_ns, median_ns and mean_ns",
+                ));
+            };
+
+            let day = parsers::number(line_idx, line, day)?;
+            let phase = phase
+                .parse()
+                .map_err(|_| ParseError::at(line_idx, line, phase, "parse, part1 or part2"))?;
+            let nanos = |field| parsers::number(line_idx, line, field).map(Duration::from_nanos);
+            let stats = Stats {
+                min: nanos(min)?,
+                median: nanos(median)?,
+                mean: nanos(mean)?,
+            };
+
+            baseline.insert((day, phase), stats);
+        }
+
+        Ok(Self(baseline))
+    }
+
+    #[must_use]
+    pub fn get(&self, day: u8, phase: Phase) -> Option<&Stats> {
+        self.0.get(&(day, phase))
+    }
+}
+
+impl FromIterator<Measurement> for Baseline {
+    fn from_iter<T: IntoIterator<Item = Measurement>>(iter: T) -> Self {
+        Self(
+            iter.into_iter()
+                .map(|m| ((m.day, m.phase), m.stats))
+                .collect(),
+        )
+    }
+}
+
+/// Serialises back into the format [`Baseline::parse`] reads.
+impl Display for Baseline {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "# day phase min_ns median_ns mean_ns")?;
+        for ((day, phase), stats) in &self.0 {
+            writeln!(
+                f,
+                "{day} {phase} {} {} {}",
+                stats.min.as_nanos(),
+                stats.median.as_nanos(),
+                stats.mean.as_nanos()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{bench_day, Baseline, Stats};
+    use crate::InputSource;
+
+    #[test]
+    fn test_stats() {
+        let samples = [4, 1, 3, 2].map(Duration::from_millis).to_vec();
+        let stats = Stats::from_samples(samples);
+
+        assert_eq!(Duration::from_millis(1), stats.min);
+        assert_eq!(Duration::from_micros(2500), stats.median);
+        assert_eq!(Duration::from_micros(2500), stats.mean);
+
+        let slower = Stats::from_samples(vec![Duration::from_millis(3)]);
+        assert!((slower.change(&stats) - 20.0).abs() < 1e-9);
+    }
+
+    #[cfg(feature = "day-9")]
+    #[test]
+    fn test_bench_day_round_trip() {
+        use super::Phase;
+        use crate::Part;
+
+        let input = InputSource::Text("0 3 6 9 12 15\n1 3 6 10 15 21");
+        let measurements = bench_day(9, &input, 3).unwrap();
+
+        assert_eq!(
+            vec![
+                Phase::Parse,
+                Phase::Solve(Part::First),
+                Phase::Solve(Part::Second)
+            ],
+            measurements.iter().map(|m| m.phase).collect::<Vec<_>>()
+        );
+
+        let baseline = measurements.into_iter().collect::<Baseline>();
+        assert_eq!(baseline, Baseline::parse(&baseline.to_string()).unwrap());
+    }
+
+    #[test]
+    fn test_bench_day_errors() {
+        assert!(bench_day(9, &InputSource::Text("0 3 x"), 3).is_err());
+        assert!(Baseline::parse("5 parse 1 2").is_err());
+        assert!(Baseline::parse("5 part3 1 2 3").is_err());
+    }
+}
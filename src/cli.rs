@@ -0,0 +1,1203 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use itertools::Itertools;
+
+use crate::{
+    answers::{self, Answers, Status},
+    bench::{bench_day, Baseline, Stats},
+    examples,
+    fetch::{self, DefaultClient, Fetched, Fetcher},
+    input, json,
+    ledger::{self, Judgement, Ledger, Verdict},
+    registry, render, repl,
+    runner::{self, Outcome},
+    scaffold::{create_day, NewDay},
+    trace::Level,
+    watch::Watcher,
+    Answer, AocError, InputSource, Part,
+};
+
+pub const USAGE: &str = "\
+Usage: aoc_2023 [run] [DAYS...] [OPTIONS]
+       aoc_2023 bench [DAYS...] [BENCH OPTIONS]
+       aoc_2023 new-day N [NEW-DAY OPTIONS]
+       aoc_2023 fetch DAYS... [FETCH OPTIONS]
+       aoc_2023 repl DAY [--input PATH]
+       aoc_2023 watch DAY [--input PATH]
+       aoc_2023 ledger DAY [PART ANSWER] [LEDGER OPTIONS]
+
+Days:
+  5          a single day
+  3..7       a range of days, end exclusive
+  3..=7      a range of days, end inclusive
+  --all      every day compiled in (the default)
+
+Options:
+  --part <1|2>     only solve one part
+  --input <PATH>   read input from PATH, or stdin for -, instead of res/day_N.txt
+                   (single day only)
+  --input-dir <DIR>
+                   read day_N.txt files from DIR instead of res/
+  --check          compare answers against res/answers.toml and print a table
+  --answers <PATH> read expected answers from PATH (implies --check)
+  --ledger <PATH>  with --check, read submitted answers from PATH instead of
+                   res/ledger.toml, to warn about known-wrong answers and to check
+                   against the correct ones where the answers file has none
+  --format <FORMAT>
+                   print results as text (the default), json (one array) or jsonl
+                   (one object per line); records hold day, part, answer,
+                   elapsed_ns and error
+  -j, --jobs <N>   solve days and parts on N worker threads, 0 for one per core
+                   (default 1)
+  --explain <LEVEL>
+                   print what the solvers did to stderr: 1 for a summary, 2 for
+                   each step, 3 for everything
+  --render <PATH>  draw the solved puzzle to PATH as a .ppm or .svg image, as
+                   coloured text for any other name, or to stdout for -
+                   (single day only)
+  -h, --help       print this message
+
+Bench options:
+  --iterations <N> time each phase N times (default 10)
+  --save <PATH>    write the timings to PATH as a baseline
+  --baseline <PATH>
+                   compare median timings against a saved baseline
+  --threshold <PCT>
+                   report slowdowns above PCT percent as regressions (default 10)
+
+New-day options:
+  --example <PATH> add the example in PATH, or stdin for -, to res/examples;
+                   needs --first, --second or both
+  --first <ANSWER> expected part one answer for the example
+  --second <ANSWER>
+                   expected part two answer for the example
+
+Ledger options:
+  --record <VERDICT>
+                   record the site's verdict on ANSWER: correct, wrong, too-high
+                   or too-low; without it, only say what the ledger knows of it
+  --ledger <PATH>  use PATH instead of res/ledger.toml
+
+Fetch options:
+  --session-file <PATH>
+                   read the session cookie from PATH (default .aoc-session)
+  --base-url <URL> download from URL instead of https://adventofcode.com/2023
+
+Repl:
+  Loads the day's input once, then reads queries from stdin, such as \"next AAA LR\"
+  for day 8; help lists them
+
+Watch:
+  Solves the day again, with timings, whenever its input or the files in
+  res/examples for the day change, showing the answers that changed
+
+Environment:
+  AOC_INPUT_DIR    default directory for day_N.txt files when --input-dir is absent
+  AOC_SESSION      session cookie for fetch, used instead of the session file";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Run(RunArgs),
+    Bench(BenchArgs),
+    NewDay(NewDayArgs),
+    Fetch(FetchArgs),
+    Repl(ReplArgs),
+    Watch(WatchArgs),
+    Ledger(LedgerArgs),
+    Help,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunArgs {
+    pub days: Vec<u8>,
+    pub parts: Vec<Part>,
+    pub input: InputSource<'static>,
+    pub check: Option<PathBuf>,
+    pub jobs: usize,
+    pub format: Format,
+    pub explain: Option<Level>,
+    pub render: Option<PathBuf>,
+    pub ledger: PathBuf,
+}
+
+/// How `run` prints its results.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    JsonLines,
+}
+
+impl std::str::FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "jsonl" => Ok(Self::JsonLines),
+            x => Err(format!("{x} is not a valid format")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BenchArgs {
+    pub days: Vec<u8>,
+    pub input: InputSource<'static>,
+    pub iterations: usize,
+    pub save: Option<PathBuf>,
+    pub baseline: Option<PathBuf>,
+    pub threshold: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewDayArgs {
+    pub day: u8,
+    pub example: Option<InputSource<'static>>,
+    pub first: Option<Answer>,
+    pub second: Option<Answer>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FetchArgs {
+    pub days: Vec<u8>,
+    pub session_file: PathBuf,
+    pub base_url: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplArgs {
+    pub day: u8,
+    pub input: InputSource<'static>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchArgs {
+    pub day: u8,
+    pub input: InputSource<'static>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LedgerArgs {
+    pub day: u8,
+    /// The answer to look up or record, with its part.
+    pub answer: Option<(Part, Answer)>,
+    pub record: Option<Verdict>,
+    pub ledger: PathBuf,
+}
+
+impl Command {
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut args = args.into_iter().peekable();
+
+        match args.peek().map(String::as_str) {
+            Some("run") => {
+                args.next();
+                parse_run(args)
+            }
+            Some("bench") => {
+                args.next();
+                parse_bench(args)
+            }
+            Some("new-day") => {
+                args.next();
+                parse_new_day(args)
+            }
+            Some("fetch") => {
+                args.next();
+                parse_fetch(args)
+            }
+            Some("repl") => {
+                args.next();
+                parse_repl(args)
+            }
+            Some("watch") => {
+                args.next();
+                parse_watch(args)
+            }
+            Some("ledger") => {
+                args.next();
+                parse_ledger(args)
+            }
+            Some("help" | "-h" | "--help") => Ok(Self::Help),
+            _ => parse_run(args),
+        }
+    }
+}
+
+fn parse_run(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut selection = Selection::default();
+    let mut parts = Part::BOTH.to_vec();
+    let mut check = None;
+    let mut jobs = 1;
+    let mut format = Format::default();
+    let mut explain = None;
+    let mut render = None;
+    let mut ledger = PathBuf::from(ledger::DEFAULT_PATH);
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--part" => {
+                let part = value(&mut args, "--part")?;
+                let part = part
+                    .parse::<u8>()
+                    .map_err(|_| format!("{part} is not a valid part"))
+                    .and_then(Part::try_from)?;
+                parts = vec![part];
+            }
+            "--check" => {
+                check.get_or_insert_with(|| PathBuf::from(answers::DEFAULT_PATH));
+            }
+            "--answers" => check = Some(PathBuf::from(value(&mut args, "--answers")?)),
+            "-j" | "--jobs" => jobs = number(&mut args, "--jobs")?,
+            "--format" => format = value(&mut args, "--format")?.parse()?,
+            "--explain" => {
+                explain = Some(Level::try_from(number::<u8>(&mut args, "--explain")?)?);
+            }
+            "--render" => render = Some(PathBuf::from(value(&mut args, "--render")?)),
+            "--ledger" => ledger = PathBuf::from(value(&mut args, "--ledger")?),
+            _ => selection.accept(&arg, &mut args)?,
+        }
+    }
+
+    if check.is_some() && format != Format::Text {
+        return Err("--check only supports text output".to_string());
+    }
+
+    let (days, input) = selection.finish()?;
+
+    if render.is_some() {
+        if days.len() != 1 {
+            return Err("--render can only be used with a single day".to_string());
+        }
+        // The input is read again to draw it, which stdin can't do.
+        if input == InputSource::Stdin {
+            return Err("--render can't be used with --input -".to_string());
+        }
+    }
+
+    Ok(Command::Run(RunArgs {
+        days,
+        parts,
+        input,
+        check,
+        jobs,
+        format,
+        explain,
+        render,
+        ledger,
+    }))
+}
+
+fn parse_bench(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut selection = Selection::default();
+    let mut iterations = 10;
+    let mut save = None;
+    let mut baseline = None;
+    let mut threshold = 10;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--iterations" => iterations = number(&mut args, "--iterations")?,
+            "--save" => save = Some(PathBuf::from(value(&mut args, "--save")?)),
+            "--baseline" => baseline = Some(PathBuf::from(value(&mut args, "--baseline")?)),
+            "--threshold" => threshold = number(&mut args, "--threshold")?,
+            _ => selection.accept(&arg, &mut args)?,
+        }
+    }
+
+    if iterations == 0 {
+        return Err("--iterations must be at least 1".to_string());
+    }
+
+    let (days, input) = selection.finish()?;
+
+    Ok(Command::Bench(BenchArgs {
+        days,
+        input,
+        iterations,
+        save,
+        baseline,
+        threshold,
+    }))
+}
+
+fn parse_new_day(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut day = None;
+    let mut example = None;
+    let mut first = None;
+    let mut second = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--example" => example = Some(InputSource::from_arg(&value(&mut args, "--example")?)),
+            "--first" => first = Some(number(&mut args, "--first")?),
+            "--second" => second = Some(number(&mut args, "--second")?),
+            flag if flag.starts_with('-') => return Err(format!("unknown option {flag}")),
+            number if day.is_none() => {
+                day = Some(
+                    number
+                        .parse::<u8>()
+                        .ok()
+                        .filter(|day| (1..=25).contains(day))
+                        .ok_or_else(|| format!("{number} is not a valid day"))?,
+                );
+            }
+            extra => return Err(format!("unexpected argument {extra}")),
+        }
+    }
+
+    if example.is_some() && first.is_none() && second.is_none() {
+        return Err("--example needs --first or --second to check it against".to_string());
+    }
+
+    Ok(Command::NewDay(NewDayArgs {
+        day: day.ok_or("new-day expects a day number")?,
+        example,
+        first,
+        second,
+    }))
+}
+
+fn parse_fetch(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut days = Vec::new();
+    let mut session_file = PathBuf::from(fetch::SESSION_FILE);
+    let mut base_url = fetch::BASE_URL.to_string();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--session-file" => {
+                session_file = PathBuf::from(value(&mut args, "--session-file")?);
+            }
+            "--base-url" => base_url = value(&mut args, "--base-url")?,
+            flag if flag.starts_with('-') => return Err(format!("unknown option {flag}")),
+            selector => days.extend(parse_days(selector)?),
+        }
+    }
+
+    // Days are checked against the calendar rather than the registry, so inputs can
+    // be fetched before the solution exists
+    if let Some(day) = days.iter().find(|day| !(1..=25).contains(*day)) {
+        return Err(format!("{day} is not a valid day"));
+    }
+    if days.is_empty() {
+        return Err("fetch expects at least one day".to_string());
+    }
+    days.sort_unstable();
+    days.dedup();
+
+    Ok(Command::Fetch(FetchArgs {
+        days,
+        session_file,
+        base_url,
+    }))
+}
+
+fn parse_repl(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut selection = Selection::default();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            arg => selection.accept(arg, &mut args)?,
+        }
+    }
+
+    selection.days.sort_unstable();
+    selection.days.dedup();
+    if selection.days.len() != 1 {
+        return Err("repl expects a single day".to_string());
+    }
+    let (days, input) = selection.finish()?;
+    // Queries are read from stdin, so it can't hold the puzzle input as well.
+    if input == InputSource::Stdin {
+        return Err("repl can't be used with --input -".to_string());
+    }
+
+    Ok(Command::Repl(ReplArgs {
+        day: days[0],
+        input,
+    }))
+}
+
+fn parse_watch(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut selection = Selection::default();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            arg => selection.accept(arg, &mut args)?,
+        }
+    }
+
+    selection.days.sort_unstable();
+    selection.days.dedup();
+    if selection.days.len() != 1 {
+        return Err("watch expects a single day".to_string());
+    }
+    let (days, input) = selection.finish()?;
+    if input.path(days[0]).is_none() {
+        return Err("watch can't be used with --input -".to_string());
+    }
+
+    Ok(Command::Watch(WatchArgs {
+        day: days[0],
+        input,
+    }))
+}
+
+fn parse_ledger(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut positional = Vec::new();
+    let mut record = None;
+    let mut ledger = PathBuf::from(ledger::DEFAULT_PATH);
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--record" => {
+                // Spelled with a dash on the command line, so it needs no quotes.
+                record = Some(value(&mut args, "--record")?.replace('-', " ").parse()?);
+            }
+            "--ledger" => ledger = PathBuf::from(value(&mut args, "--ledger")?),
+            flag if flag.starts_with('-') && flag.parse::<i64>().is_err() => {
+                return Err(format!("unknown option {flag}"));
+            }
+            _ => positional.push(arg),
+        }
+    }
+
+    let (day, answer) = match positional.as_slice() {
+        [day] => (day, None),
+        [day, part, answer] => {
+            let part = part
+                .parse::<u8>()
+                .map_err(|_| format!("{part} is not a valid part"))
+                .and_then(Part::try_from)?;
+            let answer = answer
+                .parse::<Answer>()
+                .map_err(|_| format!("{answer} is not a valid answer"))?;
+            (day, Some((part, answer)))
+        }
+        _ => return Err("ledger expects a day, then optionally a part and answer".to_string()),
+    };
+    let day = day
+        .parse::<u8>()
+        .ok()
+        .filter(|day| (1..=25).contains(day))
+        .ok_or_else(|| format!("{day} is not a valid day"))?;
+    if record.is_some() && answer.is_none() {
+        return Err("--record needs a part and answer".to_string());
+    }
+
+    Ok(Command::Ledger(LedgerArgs {
+        day,
+        answer,
+        record,
+        ledger,
+    }))
+}
+
+fn value(args: &mut impl Iterator<Item = String>, flag: &str) -> Result<String, String> {
+    args.next().ok_or_else(|| format!("{flag} expects a value"))
+}
+
+fn number<T: std::str::FromStr>(
+    args: &mut impl Iterator<Item = String>,
+    flag: &str,
+) -> Result<T, String> {
+    let value = value(args, flag)?;
+    value
+        .parse()
+        .map_err(|_| format!("{value} is not a valid value for {flag}"))
+}
+
+/// The day selectors and input options shared by every subcommand.
+#[derive(Default)]
+struct Selection {
+    days: Vec<u8>,
+    input: InputSource<'static>,
+}
+
+impl Selection {
+    fn accept(&mut self, arg: &str, args: &mut impl Iterator<Item = String>) -> Result<(), String> {
+        match arg {
+            "--all" => self.days.extend(registry::all().iter().map(|d| d.number)),
+            "--input" => self.input = InputSource::from_arg(&value(args, "--input")?),
+            "--input-dir" => {
+                self.input = InputSource::Dir(PathBuf::from(value(args, "--input-dir")?));
+            }
+            flag if flag.starts_with('-') => return Err(format!("unknown option {flag}")),
+            selector => self.days.extend(parse_days(selector)?),
+        }
+        Ok(())
+    }
+
+    /// Falls back to every day when no selector was given, as `parse_days` never
+    /// returns an empty selection.
+    fn finish(mut self) -> Result<(Vec<u8>, InputSource<'static>), String> {
+        if self.days.is_empty() {
+            self.days.extend(registry::all().iter().map(|d| d.number));
+        }
+        self.days.sort_unstable();
+        self.days.dedup();
+
+        for &day in &self.days {
+            registry::find(day)?;
+        }
+        if self.input.is_single() && self.days.len() != 1 {
+            return Err("--input can only be used with a single day".to_string());
+        }
+
+        Ok((self.days, self.input))
+    }
+}
+
+fn parse_days(selector: &str) -> Result<Vec<u8>, String> {
+    let parse_day = |day: &str| {
+        day.parse::<u8>()
+            .map_err(|_| format!("{day} is not a valid day"))
+    };
+
+    let days: Vec<u8> = if let Some((start, end)) = selector.split_once("..=") {
+        (parse_day(start)?..=parse_day(end)?).collect()
+    } else if let Some((start, end)) = selector.split_once("..") {
+        (parse_day(start)?..parse_day(end)?).collect()
+    } else {
+        vec![parse_day(selector)?]
+    };
+
+    // An empty selection would otherwise fall back to every day.
+    if days.is_empty() {
+        return Err(format!("{selector} selects no days"));
+    }
+    Ok(days)
+}
+
+#[must_use]
+pub fn run(command: Command) -> ExitCode {
+    match command {
+        Command::Help => {
+            println!("{USAGE}");
+            ExitCode::SUCCESS
+        }
+        Command::Run(args) => run_days(&args),
+        Command::Bench(args) => run_bench(&args),
+        Command::NewDay(args) => run_new_day(&args),
+        Command::Fetch(args) => run_fetch(&args),
+        Command::Repl(args) => run_repl(&args),
+        Command::Watch(args) => run_watch(args),
+        Command::Ledger(args) => run_ledger(&args),
+    }
+}
+
+fn run_ledger(args: &LedgerArgs) -> ExitCode {
+    let mut ledger = match Ledger::load(&args.ledger) {
+        Ok(ledger) => ledger,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let Some((part, answer)) = args.answer else {
+        for part in Part::BOTH {
+            let attempts = ledger.attempts(args.day, part);
+            if !attempts.is_empty() {
+                let attempts = attempts
+                    .iter()
+                    .map(|attempt| format!("{} {}", attempt.value, attempt.verdict))
+                    .join(", ");
+                println!("Day {} [{}/2]: {attempts}", args.day, part.number());
+            }
+        }
+        return ExitCode::SUCCESS;
+    };
+
+    let label = format!("Day {} [{}/2]", args.day, part.number());
+    let judgement = ledger.judge(args.day, part, answer);
+    if judgement.is_rejected() {
+        eprintln!("{label}: {answer} is {judgement}, not submitting it");
+        return ExitCode::FAILURE;
+    }
+    if matches!(judgement, Judgement::Tried(_)) {
+        eprintln!("{label}: warning: {answer} is {judgement}");
+    } else if args.record.is_none() {
+        println!("{label}: {answer} is {judgement}");
+    }
+
+    let Some(verdict) = args.record else {
+        return ExitCode::SUCCESS;
+    };
+    ledger.record(args.day, part, answer, verdict);
+    match ledger.save(&args.ledger) {
+        Ok(()) => {
+            println!("{label}: recorded {answer} as {verdict}");
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run_watch(args: WatchArgs) -> ExitCode {
+    let mut watcher = Watcher::new(args.day, args.input, examples::DEFAULT_DIR);
+    let mut previous = None;
+
+    println!(
+        "Day {}: watching its input and examples, ^C to stop",
+        args.day
+    );
+    loop {
+        let run = watcher.run();
+        print!("{}", run.report(previous.as_ref()));
+        previous = Some(run);
+
+        let changed = watcher.wait();
+        println!();
+        for path in changed {
+            println!("changed: {}", path.display());
+        }
+    }
+}
+
+fn run_repl(args: &ReplArgs) -> ExitCode {
+    let solver = match runner::build_model(args.day, &args.input) {
+        Ok(solver) => solver,
+        Err(e) => {
+            eprintln!("Day {}: {e}", args.day);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match repl::run(args.day, &*solver, io::stdin().lock(), io::stdout().lock()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run_fetch(args: &FetchArgs) -> ExitCode {
+    let session = match fetch::load_session(&args.session_file) {
+        Ok(session) => session,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut fetcher =
+        Fetcher::new(DefaultClient, session, input::default_dir()).with_base_url(&args.base_url);
+    let mut failed = false;
+
+    for &day in &args.days {
+        match fetcher.fetch(day) {
+            Ok(Fetched::Cached(path)) => println!("Day {day}: cached at {}", path.display()),
+            Ok(Fetched::Downloaded(path)) => println!("Day {day}: wrote {}", path.display()),
+            Err(e) => {
+                eprintln!("Day {day}: {e}");
+                failed = true;
+            }
+        }
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn run_new_day(args: &NewDayArgs) -> ExitCode {
+    let example = match args.example.as_ref().map(|example| example.lines(args.day)) {
+        None => Vec::new(),
+        Some(Ok(example)) => example,
+        Some(Err(e)) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let new_day = NewDay {
+        day: args.day,
+        example,
+        first: args.first,
+        second: args.second,
+    };
+
+    match create_day(Path::new("."), &new_day) {
+        Ok(changed) => {
+            for path in changed {
+                println!("wrote {}", path.display());
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run_days(args: &RunArgs) -> ExitCode {
+    let expected = match &args.check {
+        Some(path) => match load_expected(path, &args.ledger) {
+            Ok(expected) => Some(expected),
+            Err(e) => {
+                eprintln!("{e}");
+                return ExitCode::FAILURE;
+            }
+        },
+        None => None,
+    };
+
+    let outcomes = runner::run_days(
+        &args.days,
+        &args.input,
+        &args.parts,
+        args.jobs,
+        args.explain,
+    );
+    print_explanations(&outcomes);
+
+    let mut failed = match (expected, args.format) {
+        (Some((answers, ledger)), _) => {
+            print_ledger_warnings(&outcomes, &ledger);
+            print_check_table(&outcomes, &answers)
+        }
+        (None, Format::Text) => print_outcomes(&outcomes),
+        (None, Format::Json) => {
+            println!(
+                "{}",
+                json::Value::Array(outcomes.iter().map(Outcome::to_json).collect())
+            );
+            outcomes.iter().any(|outcome| outcome.result.is_err())
+        }
+        (None, Format::JsonLines) => {
+            for outcome in &outcomes {
+                println!("{}", outcome.to_json());
+            }
+            outcomes.iter().any(|outcome| outcome.result.is_err())
+        }
+    };
+
+    if let Some(path) = &args.render {
+        let rendered = runner::render_day(args.days[0], &args.input)
+            .and_then(|canvas| render::save(&canvas, path).map_err(|e| e.to_string()));
+        if let Err(e) = rendered {
+            eprintln!("Day {}: {e}", args.days[0]);
+            failed = true;
+        }
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Writes the traced events to stderr, so they never mix with the answers.
+fn print_explanations(outcomes: &[Outcome]) {
+    for outcome in outcomes.iter().filter(|outcome| !outcome.events.is_empty()) {
+        eprintln!("Day {} [{}/2]:", outcome.day, outcome.part.number());
+        for event in &outcome.events {
+            eprintln!("  {event}");
+        }
+    }
+}
+
+/// The answers `--check` compares against: the answers file, with the ledger's
+/// correct answers for the parts it has none for. The answers file may be missing as
+/// long as the ledger has answers of its own.
+fn load_expected(answers: &Path, ledger: &Path) -> Result<(Answers, Ledger), AocError> {
+    let ledger = Ledger::load(ledger)?;
+    let accepted = ledger.answers();
+
+    let mut answers = match Answers::load(answers) {
+        Ok(answers) => answers,
+        Err(AocError::MissingInput { .. }) if !accepted.is_empty() => Answers::default(),
+        Err(e) => return Err(e),
+    };
+    answers.fill_from(accepted);
+
+    Ok((answers, ledger))
+}
+
+/// Writes to stderr which answers the ledger already knows to be wrong.
+fn print_ledger_warnings(outcomes: &[Outcome], ledger: &Ledger) {
+    for outcome in outcomes {
+        let Ok(answer) = outcome.result else {
+            continue;
+        };
+        let judgement = ledger.judge(outcome.day, outcome.part, answer);
+        if !matches!(judgement, Judgement::Untried | Judgement::Correct) {
+            eprintln!(
+                "Day {} [{}/2]: warning: {answer} is {judgement}",
+                outcome.day,
+                outcome.part.number()
+            );
+        }
+    }
+}
+
+fn print_outcomes(outcomes: &[Outcome]) -> bool {
+    let mut failed = false;
+
+    for outcome in outcomes {
+        let label = format!("Day {} [{}/2]", outcome.day, outcome.part.number());
+        match &outcome.result {
+            Ok(answer) => println!("{label}: {answer}"),
+            Err(e) => {
+                eprintln!("{label}: {e}");
+                failed = true;
+            }
+        }
+    }
+
+    failed
+}
+
+fn print_check_table(outcomes: &[Outcome], answers: &Answers) -> bool {
+    let mut failed = false;
+
+    println!(
+        "{:>3}  {:>4}  {:>16}  {:>16}  Status",
+        "Day", "Part", "Expected", "Actual"
+    );
+    for outcome in outcomes {
+        let expected = answers
+            .get(outcome.day, outcome.part)
+            .map_or_else(|| "-".to_string(), |answer| answer.to_string());
+        let (actual, status) = match &outcome.result {
+            Ok(answer) => (
+                answer.to_string(),
+                answers.check(outcome.day, outcome.part, *answer),
+            ),
+            Err(e) => (format!("error: {e}"), Status::Fail),
+        };
+        failed |= status == Status::Fail;
+
+        println!(
+            "{:>3}  {:>4}  {expected:>16}  {actual:>16}  {status}",
+            outcome.day,
+            outcome.part.number()
+        );
+    }
+
+    failed
+}
+
+fn run_bench(args: &BenchArgs) -> ExitCode {
+    let baseline = match args.baseline.as_ref().map(Baseline::load).transpose() {
+        Ok(baseline) => baseline,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut failed = false;
+    let mut measurements = Vec::new();
+
+    println!(
+        "{:>3}  {:<5}  {:>12}  {:>12}  {:>12}  {:>12}  {:>8}",
+        "Day", "Phase", "Min", "Median", "Mean", "Baseline", "Change"
+    );
+    for &number in &args.days {
+        let day_measurements = match bench_day(number, &args.input, args.iterations) {
+            Ok(day_measurements) => day_measurements,
+            Err(e) => {
+                eprintln!("Day {number}: {e}");
+                failed = true;
+                continue;
+            }
+        };
+
+        for measurement in &day_measurements {
+            let Stats { min, median, mean } = measurement.stats;
+            print!(
+                "{:>3}  {:<5}  {:>12}  {:>12}  {:>12}",
+                measurement.day,
+                measurement.phase.to_string(),
+                format!("{min:.1?}"),
+                format!("{median:.1?}"),
+                format!("{mean:.1?}")
+            );
+
+            match baseline
+                .as_ref()
+                .and_then(|b| b.get(measurement.day, measurement.phase))
+            {
+                Some(previous) => {
+                    let change = measurement.stats.change(previous);
+                    let regressed = change > f64::from(args.threshold);
+                    failed |= regressed;
+                    println!(
+                        "  {:>12}  {:>+7.1}%{}",
+                        format!("{:.1?}", previous.median),
+                        change,
+                        if regressed { "  REGRESSION" } else { "" }
+                    );
+                }
+                None if baseline.is_some() => println!("  {:>12}  {:>8}", "-", "-"),
+                None => println!(),
+            }
+        }
+
+        measurements.extend(day_measurements);
+    }
+
+    if let Some(path) = &args.save {
+        let saved = measurements.into_iter().collect::<Baseline>();
+        if let Err(e) = std::fs::write(path, saved.to_string()) {
+            eprintln!("failed to write {}: {e}", path.display());
+            failed = true;
+        }
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+// These select real days, so they need every one of them compiled in.
+#[cfg(all(test, feature = "all-days"))]
+mod tests {
+    use std::path::PathBuf;
+
+    use rstest::rstest;
+
+    use super::{
+        load_expected, BenchArgs, Command, FetchArgs, Format, LedgerArgs, NewDayArgs, ReplArgs,
+        RunArgs, WatchArgs,
+    };
+    use crate::{ledger::Verdict, trace::Level, Answer, InputSource, Part};
+
+    fn parse(args: &str) -> Result<Command, String> {
+        Command::parse(args.split_whitespace().map(str::to_string))
+    }
+
+    #[rstest]
+    #[case("run 5", vec![5], Part::BOTH.to_vec())]
+    #[case("run 3..7 --part 2", vec![3, 4, 5, 6], vec![Part::Second])]
+    #[case("3..=5 1", vec![1, 3, 4, 5], Part::BOTH.to_vec())]
+    #[case("run --all", (1..=10).collect(), Part::BOTH.to_vec())]
+    #[case("", (1..=10).collect(), Part::BOTH.to_vec())]
+    fn test_parse_run(#[case] args: &str, #[case] days: Vec<u8>, #[case] parts: Vec<Part>) {
+        assert_eq!(
+            Ok(Command::Run(RunArgs {
+                days,
+                parts,
+                input: InputSource::Default,
+                check: None,
+                jobs: 1,
+                format: Format::Text,
+                explain: None,
+                render: None,
+                ledger: PathBuf::from("res/ledger.toml"),
+            })),
+            parse(args)
+        );
+    }
+
+    #[test]
+    fn test_parse_check() {
+        let Ok(Command::Run(args)) = parse("run 1 --check") else {
+            panic!("expected a run command");
+        };
+        assert_eq!(Some(PathBuf::from("res/answers.toml")), args.check);
+
+        let Ok(Command::Run(args)) = parse("run 1 --answers mine.toml --check") else {
+            panic!("expected a run command");
+        };
+        assert_eq!(Some(PathBuf::from("mine.toml")), args.check);
+
+        let Ok(Command::Run(args)) = parse("--all -j 0") else {
+            panic!("expected a run command");
+        };
+        assert_eq!(0, args.jobs);
+
+        let Ok(Command::Run(args)) = parse("run 1 --format jsonl") else {
+            panic!("expected a run command");
+        };
+        assert_eq!(Format::JsonLines, args.format);
+
+        let Ok(Command::Run(args)) = parse("run 10 --render loop.svg") else {
+            panic!("expected a run command");
+        };
+        assert_eq!(Some(PathBuf::from("loop.svg")), args.render);
+
+        let Ok(Command::Run(args)) = parse("run 8 --explain 3") else {
+            panic!("expected a run command");
+        };
+        assert_eq!(Some(Level::Detail), args.explain);
+    }
+
+    #[test]
+    fn test_parse_bench() {
+        assert_eq!(
+            Ok(Command::Bench(BenchArgs {
+                days: vec![5, 6],
+                input: InputSource::Default,
+                iterations: 3,
+                save: Some(PathBuf::from("new.txt")),
+                baseline: Some(PathBuf::from("old.txt")),
+                threshold: 25,
+            })),
+            parse("bench 5..=6 --iterations 3 --save new.txt --baseline old.txt --threshold 25")
+        );
+    }
+
+    #[test]
+    fn test_parse_new_day() {
+        assert_eq!(
+            Ok(Command::NewDay(NewDayArgs {
+                day: 11,
+                example: Some(InputSource::Path(PathBuf::from("example.txt"))),
+                first: Some(Answer::Unsigned(374)),
+                second: None,
+            })),
+            parse("new-day 11 --example example.txt --first 374")
+        );
+    }
+
+    #[test]
+    fn test_parse_fetch() {
+        assert_eq!(
+            Ok(Command::Fetch(FetchArgs {
+                days: vec![11, 12, 13],
+                session_file: PathBuf::from("cookie.txt"),
+                base_url: "http://localhost:8080/2023".to_string(),
+            })),
+            parse(
+                "fetch 12..=13 11 --session-file cookie.txt --base-url http://localhost:8080/2023"
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_repl() {
+        assert_eq!(
+            Ok(Command::Repl(ReplArgs {
+                day: 8,
+                input: InputSource::Path(PathBuf::from("example.txt")),
+            })),
+            parse("repl 8 --input example.txt")
+        );
+        assert!(matches!(parse("repl 8 8"), Ok(Command::Repl(_))));
+    }
+
+    #[test]
+    fn test_parse_watch() {
+        assert_eq!(
+            Ok(Command::Watch(WatchArgs {
+                day: 8,
+                input: InputSource::Dir(PathBuf::from("inputs")),
+            })),
+            parse("watch 8 --input-dir inputs")
+        );
+        assert!(matches!(parse("watch 8 8"), Ok(Command::Watch(_))));
+    }
+
+    #[test]
+    fn test_load_expected() {
+        let dir = std::env::temp_dir().join(format!("aoc_2023_expected_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let (answers, ledger) = (dir.join("answers.toml"), dir.join("ledger.toml"));
+
+        // Neither file, so nothing to check against.
+        assert!(load_expected(&answers, &ledger).is_err());
+
+        std::fs::write(
+            &ledger,
+            "[[attempt]]\nday = 9\npart = 1\nvalue = 18\nverdict = \"correct\"\n",
+        )
+        .unwrap();
+        let (expected, _) = load_expected(&answers, &ledger).unwrap();
+        assert_eq!(Some(Answer::Unsigned(18)), expected.get(9, Part::First));
+
+        std::fs::write(&answers, "[[answer]]\nday = 9\npart = 1\nvalue = 17\n").unwrap();
+        let (expected, _) = load_expected(&answers, &ledger).unwrap();
+        assert_eq!(Some(Answer::Unsigned(17)), expected.get(9, Part::First));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_parse_ledger() {
+        assert_eq!(
+            Ok(Command::Ledger(LedgerArgs {
+                day: 9,
+                answer: Some((Part::Second, Answer::Signed(-3))),
+                record: Some(Verdict::TooLow),
+                ledger: PathBuf::from("mine.toml"),
+            })),
+            parse("ledger 9 2 -3 --record too-low --ledger mine.toml")
+        );
+        assert_eq!(
+            Ok(Command::Ledger(LedgerArgs {
+                day: 9,
+                answer: None,
+                record: None,
+                ledger: PathBuf::from("res/ledger.toml"),
+            })),
+            parse("ledger 9")
+        );
+    }
+
+    #[rstest]
+    #[case("run 0")]
+    #[case("run x")]
+    #[case("run 5..5")]
+    #[case("run 7..3")]
+    #[case("run 7..=3")]
+    #[case("fetch 4..4")]
+    #[case("run 5 --part 3")]
+    #[case("run 1..3 --input foo.txt")]
+    #[case("run 1..3 --input -")]
+    #[case("run --frobnicate")]
+    #[case("run --format xml")]
+    #[case("run --format json --check")]
+    #[case("run 3..5 --render out.ppm")]
+    #[case("run 10 --input - --render out.ppm")]
+    #[case("run 10 --render")]
+    #[case("run 8 --explain 4")]
+    #[case("bench --iterations 0")]
+    #[case("bench --part 1")]
+    #[case("new-day")]
+    #[case("new-day 26")]
+    #[case("new-day 11 12")]
+    #[case("new-day 11 --example example.txt")]
+    #[case("fetch")]
+    #[case("fetch 26")]
+    #[case("fetch 1 --all")]
+    #[case("repl")]
+    #[case("repl 7..9")]
+    #[case("repl 8 --input -")]
+    #[case("watch 1..3")]
+    #[case("watch 8 --input -")]
+    #[case("ledger")]
+    #[case("ledger 9 2")]
+    #[case("ledger 9 3 18")]
+    #[case("ledger 9 --record correct")]
+    #[case("ledger 9 2 18 --record close")]
+    fn test_parse_errors(#[case] args: &str) {
+        assert!(parse(args).is_err());
+    }
+}
@@ -1,106 +1,109 @@
+use std::io::BufRead;
+
+use crate::{input::LineReader, solution::LineSolution, AocError, ParseError, Solution};
+
+const WORDS: [(&str, u32); 10] = [
+    ("zero", 0),
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+/// Calibration totals, summed as each line is read.
+#[derive(Default)]
 pub struct Solver {
-    data: Vec<String>,
+    first: Total,
+    second: Total,
 }
 
-impl Solver {
-    #[must_use]
-    pub fn new(data: Vec<String>) -> Self {
-        Self { data }
+/// A running sum that remembers the first line without any digits, since that part
+/// has no answer.
+#[derive(Default)]
+struct Total {
+    sum: u32,
+    missing: Option<usize>,
+}
+
+impl Total {
+    fn add(&mut self, line_idx: usize, value: Option<u32>) {
+        match value {
+            Some(value) => self.sum += value,
+            None => {
+                self.missing.get_or_insert(line_idx);
+            }
+        }
     }
 
-    #[must_use]
-    pub fn solve_first(&self) -> u32 {
-        self.data
-            .iter()
-            .map(|l| {
-                let digits = l.chars().filter_map(|c| c.to_digit(10)).collect::<Vec<_>>();
-                digits[0] * 10 + digits[digits.len() - 1]
-            })
-            .sum()
+    fn get(&self) -> u32 {
+        if let Some(line_idx) = self.missing {
+            panic!("line {} has no digits", line_idx + 1);
+        }
+        self.sum
     }
+}
 
-    #[must_use]
-    #[allow(clippy::too_many_lines)]
-    pub fn solve_second(&self) -> u32 {
-        self.data
-            .iter()
-            .map(|l| {
-                let mut digits = Vec::new();
+impl LineSolution for Solver {
+    fn push_line(&mut self, line_idx: usize, line: &str) -> Result<(), ParseError> {
+        if let Some((column, _)) = line
+            .chars()
+            .enumerate()
+            .find(|(_, c)| !c.is_ascii_alphanumeric())
+        {
+            return Err(ParseError::new(
+                line_idx + 1,
+                column + 1,
+                "a letter or digit",
+            ));
+        }
 
-                let chars = l.chars().collect::<Vec<_>>();
+        self.first.add(line_idx, calibration_value(line, false));
+        self.second.add(line_idx, calibration_value(line, true));
+        Ok(())
+    }
+}
 
-                for (char_idx, next_char) in chars.iter().enumerate() {
-                    if let Some(digit) = next_char.to_digit(10) {
-                        digits.push(digit);
-                    } else {
-                        if chars.get(char_idx + 2).is_some() {
-                            // Find three letter digits
-                            let maybe_word =
-                                chars[char_idx..char_idx + 3].iter().collect::<String>();
-                            match maybe_word.as_str() {
-                                "one" => digits.push(1),
-                                "two" => digits.push(2),
-                                "six" => digits.push(6),
-                                _ => {}
-                            }
-                        }
-                        if chars.get(char_idx + 3).is_some() {
-                            // Find four letter digits
-                            let maybe_word =
-                                chars[char_idx..char_idx + 4].iter().collect::<String>();
-                            match maybe_word.as_str() {
-                                "zero" => digits.push(0),
-                                "four" => digits.push(4),
-                                "five" => digits.push(5),
-                                "nine" => digits.push(9),
-                                _ => {}
-                            }
-                        }
-                        if chars.get(char_idx + 4).is_some() {
-                            // Find five letter digits
-                            let maybe_word =
-                                chars[char_idx..char_idx + 5].iter().collect::<String>();
-                            match maybe_word.as_str() {
-                                "three" => digits.push(3),
-                                "seven" => digits.push(7),
-                                "eight" => digits.push(8),
-                                _ => {}
-                            }
-                        }
-                    }
-                }
+impl Solution for Solver {
+    type First = u32;
+    type Second = u32;
 
-                digits[0] * 10 + digits[digits.len() - 1]
-            })
-            .sum()
+    fn try_new(data: Vec<String>) -> Result<Self, ParseError> {
+        Self::from_strs(data.iter().map(String::as_str))
+    }
+
+    fn try_from_lines<R: BufRead>(lines: LineReader<R>) -> Result<Self, AocError> {
+        Self::stream(lines)
     }
-}
 
-#[test]
-fn test_solve_first() {
-    let data = vec![
-        "1abc2".to_string(),
-        "pqr3stu8vwx".to_string(),
-        "a1b2c3d4e5f".to_string(),
-        "treb7uchet".to_string(),
-    ];
+    fn solve_first(&self) -> u32 {
+        self.first.get()
+    }
 
-    let solver = Solver::new(data);
-    assert_eq!(142, solver.solve_first());
+    fn solve_second(&self) -> u32 {
+        self.second.get()
+    }
 }
 
-#[test]
-fn test_solve_second() {
-    let data = vec![
-        "two1nine".to_string(),
-        "eightwothree".to_string(),
-        "abcone2threexyz".to_string(),
-        "xtwone3four".to_string(),
-        "4nineeightseven2".to_string(),
-        "zoneight234".to_string(),
-        "7pqrstsixteen".to_string(),
-    ];
+/// The first and last digit of the line as a two-digit number. Spelled-out digits
+/// count too if `spelled` is set, including ones that overlap like "eightwo".
+fn calibration_value(line: &str, spelled: bool) -> Option<u32> {
+    // Lines are ASCII by now, so every index is a char boundary
+    let mut digits = (0..line.len()).filter_map(|idx| {
+        let rest = &line[idx..];
+        rest.chars().next()?.to_digit(10).or_else(|| {
+            WORDS
+                .iter()
+                .find(|(word, _)| spelled && rest.starts_with(word))
+                .map(|&(_, digit)| digit)
+        })
+    });
 
-    let solver = Solver::new(data);
-    assert_eq!(281, solver.solve_second());
+    let first = digits.next()?;
+    let last = digits.next_back().unwrap_or(first);
+    Some(first * 10 + last)
 }
@@ -1,6 +1,15 @@
+use crate::{
+    grid::{self, Grid, Point},
+    parsers::{self, char_grid},
+    render::{self, Canvas, Cell, Colour, Style},
+    ParseError, Solution,
+};
+
 pub struct Solver {
-    map: Vec<Vec<Segment>>,
-    start_point: (isize, isize),
+    map: Grid<Segment>,
+    start_point: Point,
+    /// Every tile of the loop in walking order, starting with the start.
+    main_loop: Vec<Point>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -38,15 +47,6 @@ impl Orientation {
         }
     }
 
-    const fn rotate(self) -> Self {
-        match self {
-            Self::Up => Self::Right,
-            Self::Right => Self::Down,
-            Self::Down => Self::Left,
-            Self::Left => Self::Up,
-        }
-    }
-
     const fn try_update(self, segment: Segment) -> Option<Self> {
         match (self, segment) {
             (Self::Up | Self::Down, Segment::Horizontal)
@@ -109,6 +109,25 @@ impl std::fmt::Display for Segment {
     }
 }
 
+impl Segment {
+    /// The box-drawing character for the pipe, or the input character for anything
+    /// else.
+    const fn glyph(self) -> char {
+        match self {
+            Self::Horizontal => '─',
+            Self::Vertical => '│',
+            Self::Ground => '.',
+            Self::Start => 'S',
+            Self::Corner(c) => match c {
+                Corner::TopLeft => '┌',
+                Corner::TopRight => '┐',
+                Corner::BottomLeft => '└',
+                Corner::BottomRight => '┘',
+            },
+        }
+    }
+}
+
 impl TryFrom<char> for Segment {
     type Error = String;
 
@@ -127,105 +146,168 @@ impl TryFrom<char> for Segment {
     }
 }
 
-impl Solver {
-    #[must_use]
-    pub fn new(data: Vec<String>) -> Self {
-        let mut map = Vec::new();
-
-        let mut start_point = (0, 0);
-
-        for (row, line) in data.iter().enumerate() {
-            let mut segments = Vec::new();
-            for (col, segment) in line.chars().enumerate() {
-                let segment = Segment::try_from(segment).unwrap();
-                segments.push(segment);
-                if segment == Segment::Start {
-                    start_point = (row as isize, col as isize);
-                }
-            }
-            map.push(segments);
-        }
+impl Solution for Solver {
+    type First = usize;
+    type Second = u64;
 
-        Self { map, start_point }
+    fn try_new(data: Vec<String>) -> Result<Self, ParseError> {
+        Self::try_from_str(&data.join("\n"))
     }
 
-    #[must_use]
-    pub fn solve_first(&self) -> usize {
-        let main_loop = self.find_loop();
+    fn try_from_str(text: &str) -> Result<Self, ParseError> {
+        let map = parsers::parse_all(text, char_grid("a pipe segment"))?;
+
+        let starts = map
+            .iter()
+            .filter(|(_, segment)| **segment == Segment::Start)
+            .map(|(point, _)| point)
+            .take(2)
+            .collect::<Vec<_>>();
+        let start_point = match starts[..] {
+            [] => return Err(ParseError::new(1, 1, "a start tile \"S\"")),
+            [start] => start,
+            [_, other, ..] => return Err(error_at(other, "only one start tile")),
+        };
+        let main_loop = find_loop(&map, start_point)?;
+
+        Ok(Self {
+            map,
+            start_point,
+            main_loop,
+        })
+    }
 
-        main_loop.len() / 2
+    fn solve_first(&self) -> usize {
+        self.main_loop.len() / 2
     }
 
-    #[must_use]
-    pub fn solve_second(&self) -> u64 {
-        // A point is contained within the loop if, in every direction from that point, you hit the loop.
+    #[allow(clippy::cast_sign_loss, clippy::cast_possible_wrap)]
+    fn solve_second(&self) -> u64 {
+        // The loop is a simple polygon on the lattice, so the shoelace formula gives its
+        // area and Pick's theorem turns that into the number of enclosed tiles.
 
-        let mut internal_area = 0;
+        let main_loop = &self.main_loop;
 
-        let main_loop = self.find_loop();
+        let twice_area = main_loop
+            .iter()
+            .zip(main_loop.iter().cycle().skip(1))
+            .map(|(p0, p1)| (p0.row * p1.col) as isize - (p1.row * p0.col) as isize)
+            .sum::<isize>()
+            .abs();
 
-        internal_area
-    }
+        let boundary = main_loop.len() as isize;
 
-    fn find_initial_orientation(&self) -> Option<Orientation> {
-        for offset in [(-1, 0), (0, 1), (1, 0), (0, -1)] {
-            let next_position = (self.start_point.0 + offset.0, self.start_point.1 + offset.1);
+        ((twice_area - boundary) / 2 + 1) as u64
+    }
 
-            if next_position.0 < 0
-                || next_position.1 < 0
-                || next_position.0 as usize >= self.map.len()
-                || next_position.1 as usize >= self.map[0].len()
-            {
-                continue;
-            }
+    /// The main loop in colour, with the start marked and every enclosed tile filled
+    /// in. Pipes that aren't part of the loop are greyed out.
+    fn render(&self) -> Option<Canvas> {
+        let main_loop = &self.main_loop;
+
+        // Each loop tile joins the tiles before and after it, which also gives the
+        // start its real shape.
+        let mut arms = Grid::filled(self.map.width(), self.map.height(), None);
+        for (idx, &point) in main_loop.iter().enumerate() {
+            let len = main_loop.len();
+            let joined = [main_loop[(idx + 1) % len], main_loop[(idx + len - 1) % len]];
+            arms[point] = Some(
+                grid::ORTHOGONAL
+                    .map(|offset| point.offset(offset).is_some_and(|p| joined.contains(&p))),
+            );
+        }
 
-            match self
-                .map
-                .get(next_position.0 as usize)
-                .and_then(|row| row.get(next_position.1 as usize))
-            {
-                None | Some(Segment::Ground) => {}
-                Some(Segment::Start) => unreachable!("There should be only one start point"),
-                Some(_) => return Some(Orientation::from(offset)),
+        let mut cells = Vec::new();
+        for (row_idx, row) in self.map.rows().enumerate() {
+            // Scanning along a row, the inside starts and ends at each loop tile with
+            // an arm going up.
+            let mut inside = false;
+            for (col_idx, segment) in row.iter().enumerate() {
+                let point = Point::new(row_idx, col_idx);
+                let cell = match arms[point] {
+                    Some(arms) => {
+                        inside ^= arms[0];
+                        let glyph = render::box_glyph(arms).unwrap_or('S');
+                        let style = Style::fg(Colour::YELLOW);
+                        if point == self.start_point {
+                            Cell::new(glyph, style.on(Colour::RED))
+                        } else {
+                            Cell::new(glyph, style)
+                        }
+                    }
+                    None if inside => Cell::new('•', Style::fg(Colour::GREEN)),
+                    None => Cell::new(segment.glyph(), Style::fg(Colour::GREY)),
+                };
+                cells.push(cell);
             }
         }
 
-        None
+        Some(Canvas::new(self.map.width(), cells))
     }
+}
 
-    fn find_loop(&self) -> Vec<(&Segment, (isize, isize))> {
-        let mut current_orientation = self.find_initial_orientation().unwrap();
-
-        let mut current_position = self.start_point;
-
-        let mut segments = Vec::new();
-
-        loop {
-            match self
-                .map
-                .get(current_position.0 as usize)
-                .and_then(|row| row.get(current_position.1 as usize))
-            {
-                None => {}
-                Some(Segment::Start) if !segments.is_empty() => break,
-                Some(current_segment) => match current_orientation.try_update(*current_segment) {
-                    None => {
-                        // If the next piece makes no sense physically, rotate orientation until you find a valid piece
-                        current_orientation = current_orientation.rotate();
-                        continue;
-                    }
-                    Some(next_orientation) => {
-                        segments.push((current_segment, current_position));
-                        current_orientation = next_orientation;
-                        let offset = current_orientation.value();
-                        current_position =
-                            (current_position.0 + offset.0, current_position.1 + offset.1);
-                    }
-                },
+fn error_at(point: Point, expected: &str) -> ParseError {
+    ParseError::new(point.row + 1, point.col + 1, expected)
+}
+
+/// Walks the pipes from the start until they lead back to it. Every pipe joined to
+/// the start is tried in turn, since a dead end can point at it too, and if none of
+/// them leads back the error is where the first walk broke off.
+fn find_loop(map: &Grid<Segment>, start: Point) -> Result<Vec<Point>, ParseError> {
+    // Only leave the start through a pipe that connects back to it
+    let exits = grid::ORTHOGONAL
+        .into_iter()
+        .map(Orientation::from)
+        .filter(|orientation| {
+            start
+                .offset(orientation.value())
+                .and_then(|next| map.get(next))
+                .is_some_and(|&segment| {
+                    segment != Segment::Start && orientation.try_update(segment).is_some()
+                })
+        })
+        .collect::<Vec<_>>();
+    if exits.len() < 2 {
+        return Err(error_at(start, "a start joined to two pipes"));
+    }
+
+    let mut first_error = None;
+    for exit in exits {
+        match walk(map, start, exit) {
+            Ok(main_loop) => return Ok(main_loop),
+            Err(e) => {
+                first_error.get_or_insert(e);
             }
         }
+    }
+    Err(first_error.expect("the start has exits"))
+}
 
-        segments
+/// Follows the pipes from the start, leaving it towards `orientation`, failing at the
+/// first tile that doesn't continue the loop.
+fn walk(
+    map: &Grid<Segment>,
+    start: Point,
+    mut orientation: Orientation,
+) -> Result<Vec<Point>, ParseError> {
+    let mut main_loop = vec![start];
+    let mut position = start;
+    loop {
+        let Some(next) = position
+            .offset(orientation.value())
+            .filter(|&next| map.get(next).is_some())
+        else {
+            return Err(error_at(position, "a pipe that stays on the map"));
+        };
+        if next == start {
+            return Ok(main_loop);
+        }
+
+        orientation = orientation
+            .try_update(map[next])
+            .ok_or_else(|| error_at(next, "a pipe that continues the loop"))?;
+        main_loop.push(next);
+        position = next;
     }
 }
 
@@ -233,60 +315,55 @@ impl Solver {
 mod tests {
     use rstest::rstest;
 
-    use crate::day_10::Solver;
+    use crate::{day_10::Solver, render::Colour, ParseError, Solution};
 
     #[test]
-    pub fn solve_first() {
-        let data = vec![
-            ".....".to_string(),
-            ".S-7.".to_string(),
-            ".|.|.".to_string(),
-            ".L-J.".to_string(),
-            ".....".to_string(),
-        ];
-
-        let solver = Solver::new(data);
-        assert_eq!(4, solver.solve_first());
+    pub fn parse_errors() {
+        let data = vec![".....".to_string(), ".S-7x".to_string()];
+
+        assert_eq!(
+            Some(ParseError::new(2, 5, "a pipe segment")),
+            Solver::try_new(data).err()
+        );
+    }
+
+    #[rstest]
+    #[case("-7\n|.", ParseError::new(1, 1, "a start tile \"S\""))]
+    #[case("S-S", ParseError::new(1, 3, "only one start tile"))]
+    #[case("S-.", ParseError::new(1, 1, "a start joined to two pipes"))]
+    #[case("S7\n|.", ParseError::new(2, 2, "a pipe that continues the loop"))]
+    pub fn broken_loops(#[case] data: &str, #[case] expected: ParseError) {
+        let data = data.lines().map(str::to_string).collect();
+
+        assert_eq!(Some(expected), Solver::try_new(data).err());
     }
 
     #[rstest]
-    #[case(vec![
-        "...........".to_string(),
-        ".S-------7.".to_string(),
-        ".|F-----7|.".to_string(),
-        ".||.....||.".to_string(),
-        ".||.....||.".to_string(),
-        ".|L-7.F-J|.".to_string(),
-        ".|..|.|..|.".to_string(),
-        ".L--J.L--J.".to_string(),
-        "...........".to_string(),
-    ], 4)]
-    #[case(vec![
-        ".F----7F7F7F7F-7....".to_string(),
-        ".|F--7||||||||FJ....".to_string(),
-        ".||.FJ||||||||L7....".to_string(),
-        "FJL7L7LJLJ||LJ.L-7..".to_string(),
-        "L--J.L7...LJS7F-7L7.".to_string(),
-        "....F-J..F7FJ|L7L7L7".to_string(),
-        "....L7.F7||L7|.L7L7|".to_string(),
-        ".....|FJLJ|FJ|F7|.LJ".to_string(),
-        "....FJL-7.||.||||...".to_string(),
-        "....L---J.LJ.LJLJ...".to_string(),
-    ], 8)]
-    #[case(vec![
-        "FF7FSF7F7F7F7F7F---7".to_string(),
-        "L|LJ||||||||||||F--J".to_string(),
-        "FL-7LJLJ||||||LJL-77".to_string(),
-        "F--JF--7||LJLJ7F7FJ-".to_string(),
-        "L---JF-JLJ.||-FJLJJ7".to_string(),
-        "|F|F-JF---7F7-L7L|7|".to_string(),
-        "|FFJF7L7F-JF7|JL---7".to_string(),
-        "7-L-JL7||F7|L7F-7F7|".to_string(),
-        "L.L7LFJ|||||FJL7||LJ".to_string(),
-        "L7JLJL-JLJLJL--JLJ.L".to_string(),
-    ], 10)]
-    pub fn solve_second(#[case] input: Vec<String>, #[case] expected: u64) {
-        let solver = Solver::new(input);
-        assert_eq!(expected, solver.solve_second());
+    #[case(".|...\n.S-7.\n.|.|.\n.L-J.")]
+    #[case(".|...\n-S-7.\n.|.|.\n.L-J.")]
+    #[case("..|..\n.F-S-\n.|.|.\n.L-J.")]
+    pub fn decoys_next_to_the_start(#[case] data: &str) {
+        let solver = Solver::try_new(data.lines().map(str::to_string).collect()).unwrap();
+
+        assert_eq!((4, 1), (solver.solve_first(), solver.solve_second()));
+    }
+
+    #[test]
+    pub fn render_fills_enclosed_tiles() {
+        let data = std::fs::read_to_string("res/examples/day_10_c.txt").unwrap();
+        let solver = Solver::try_new(data.lines().map(str::to_string).collect()).unwrap();
+        let canvas = solver.render().unwrap();
+
+        let enclosed = canvas
+            .iter()
+            .filter(|(_, cell)| cell.style.foreground == Colour::GREEN)
+            .count();
+        assert_eq!(solver.solve_second(), enclosed as u64);
+
+        let start = canvas[solver.start_point];
+        assert_eq!(
+            ('┌', Some(Colour::RED)),
+            (start.glyph, start.style.background)
+        );
     }
 }
@@ -1,3 +1,18 @@
+use std::io::BufRead;
+
+use winnow::{
+    combinator::{alt, cut_err, preceded, separated, separated_pair},
+    stream::Accumulate,
+    PResult, Parser,
+};
+
+use crate::{
+    input::LineReader,
+    parsers::{self, decimal, labelled, tag},
+    solution::{DynSolution, LineSolution},
+    AocError, ParseError, Solution,
+};
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum Color {
     Red,
@@ -11,143 +26,204 @@ pub struct Cube {
     value: u32,
 }
 
-pub struct Bag(Vec<Cube>);
-pub struct Game(Vec<Bag>);
-
-pub struct Solver {
-    data: Vec<Game>,
+/// The most cubes of each color shown at once during a game.
+#[derive(Default)]
+struct Maxima {
+    red: u32,
+    green: u32,
+    blue: u32,
 }
 
-impl Solver {
-    #[must_use]
-    pub fn new(data: Vec<String>) -> Self {
-        Self {
-            data: parse_source(data),
-        }
-    }
-
-    #[must_use]
-    pub fn solve_first(&self) -> usize {
+impl Maxima {
+    const fn is_possible(&self) -> bool {
         const MAX_RED: u32 = 12;
         const MAX_GREEN: u32 = 13;
         const MAX_BLUE: u32 = 14;
 
-        let mut id_sum = 0;
-
-        for (game_id, game) in self.data.iter().enumerate() {
-            let (red_max, green_max, blue_max) = find_maxima(game);
-            if red_max <= MAX_RED && green_max <= MAX_GREEN && blue_max <= MAX_BLUE {
-                id_sum += game_id + 1;
-            }
-        }
+        self.red <= MAX_RED && self.green <= MAX_GREEN && self.blue <= MAX_BLUE
+    }
 
-        id_sum
+    const fn power(&self) -> u32 {
+        self.red * self.green * self.blue
     }
+}
 
-    #[must_use]
-    pub fn solve_second(&self) -> u32 {
-        self.data.iter().fold(0, |acc, game| {
-            let (red_max, green_max, blue_max) = find_maxima(game);
+impl Accumulate<Cube> for Maxima {
+    fn initial(_capacity: Option<usize>) -> Self {
+        Self::default()
+    }
 
-            acc + (red_max * green_max * blue_max)
-        })
+    fn accumulate(&mut self, cube: Cube) {
+        let max = match cube.color {
+            Color::Red => &mut self.red,
+            Color::Green => &mut self.green,
+            Color::Blue => &mut self.blue,
+        };
+        *max = (*max).max(cube.value);
     }
 }
 
-fn find_maxima(game: &Game) -> (u32, u32, u32) {
-    let (mut red_max, mut green_max, mut blue_max) = (0, 0, 0);
-    for bag in &game.0 {
-        for cube in &bag.0 {
-            match cube.color {
-                Color::Red if cube.value > red_max => {
-                    red_max = cube.value;
-                }
-                Color::Green if cube.value > green_max => {
-                    green_max = cube.value;
-                }
-                Color::Blue if cube.value > blue_max => {
-                    blue_max = cube.value;
-                }
-                _ => {}
-            }
-        }
-    }
-    (red_max, green_max, blue_max)
+/// `Game N: ` followed by `;`-separated handfuls of `,`-separated cubes. Only the
+/// most of each color matters, so the handfuls aren't told apart.
+fn game(input: &mut &str) -> PResult<(u32, Maxima)> {
+    labelled(
+        preceded(tag("Game "), decimal),
+        separated(1.., cut_err(cube), alt((", ", "; "))),
+    )
+    .parse_next(input)
 }
 
-impl<'a> TryFrom<&'a str> for Color {
-    type Error = String;
+fn cube(input: &mut &str) -> PResult<Cube> {
+    separated_pair(decimal, tag(" "), color)
+        .map(|(value, color)| Cube { color, value })
+        .parse_next(input)
+}
 
-    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
-        match value {
-            "red" => Ok(Self::Red),
-            "green" => Ok(Self::Green),
-            "blue" => Ok(Self::Blue),
-            x => Err(format!("{x} is not a valid color")),
-        }
-    }
+fn color(input: &mut &str) -> PResult<Color> {
+    alt((
+        "red".value(Color::Red),
+        "green".value(Color::Green),
+        "blue".value(Color::Blue),
+    ))
+    .context(parsers::expected("red, green or blue"))
+    .parse_next(input)
 }
 
-fn parse_source(data: Vec<String>) -> Vec<Game> {
-    data.into_iter()
-        .filter_map(|e| parse_bag_entries(&e))
-        .map(|e| parse_entry(&e))
-        .map(Game)
-        .collect()
+/// Both answers, accumulated one game at a time.
+#[derive(Default)]
+pub struct Solver {
+    id_sum: usize,
+    power_sum: u32,
 }
 
-fn parse_entry(entry: &str) -> Vec<Bag> {
-    entry.split("; ").map(parse_bag).map(Bag).collect()
+impl LineSolution for Solver {
+    fn push_line(&mut self, line_idx: usize, line: &str) -> Result<(), ParseError> {
+        let (_, maxima) = parsers::parse_line(line_idx, line, game)?;
+
+        if maxima.is_possible() {
+            self.id_sum += line_idx + 1;
+        }
+        self.power_sum += maxima.power();
+
+        Ok(())
+    }
 }
 
-fn parse_bag_entries(entry: &str) -> Option<String> {
-    entry.split_once(": ").map(|(_, entry)| entry.to_owned())
+impl Solution for Solver {
+    type First = usize;
+    type Second = u32;
+
+    fn try_new(data: Vec<String>) -> Result<Self, ParseError> {
+        Self::from_strs(data.iter().map(String::as_str))
+    }
+
+    fn try_from_lines<R: BufRead>(lines: LineReader<R>) -> Result<Self, AocError> {
+        Self::stream(lines)
+    }
+
+    fn try_new_model(data: Vec<String>) -> Result<Box<dyn DynSolution>, ParseError> {
+        Ok(Box::new(Games::try_new(data)?))
+    }
+
+    fn solve_first(&self) -> usize {
+        self.id_sum
+    }
+
+    fn solve_second(&self) -> u32 {
+        self.power_sum
+    }
 }
 
-fn parse_bag(bag: &str) -> Vec<Cube> {
-    bag.split(", ").filter_map(parse_cube).collect::<Vec<_>>()
+/// Every game kept in memory, for the REPL to look up, where [`Solver`] keeps only
+/// the sums.
+pub struct Games(Vec<Maxima>);
+
+impl Games {
+    /// The fewest cubes of each color game `id` could have been played with.
+    fn find_maxima(&self, id: usize) -> Option<&Maxima> {
+        self.0.get(id.checked_sub(1)?)
+    }
 }
 
-fn parse_cube(cube: &str) -> Option<Cube> {
-    cube.split_once(' ')
-        .map(|(v, c)| {
-            let color = Color::try_from(c);
-            let value = v.parse::<u32>();
-            (value, color)
-        })
-        .and_then(|(value, color)| match (value, color) {
-            (Ok(value), Ok(color)) => Some(Cube { color, value }),
-            _ => None,
-        })
+impl Solution for Games {
+    type First = usize;
+    type Second = u32;
+
+    const QUERIES: &'static [(&'static str, &'static str)] =
+        &[("game N", "the fewest cubes of each color game N needs")];
+
+    fn try_new(data: Vec<String>) -> Result<Self, ParseError> {
+        data.iter()
+            .enumerate()
+            .map(|(line_idx, line)| {
+                parsers::parse_line(line_idx, line, game).map(|(_, maxima)| maxima)
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+
+    fn solve_first(&self) -> usize {
+        (1..)
+            .zip(&self.0)
+            .filter(|(_, maxima)| maxima.is_possible())
+            .map(|(id, _)| id)
+            .sum()
+    }
+
+    fn solve_second(&self) -> u32 {
+        self.0.iter().map(Maxima::power).sum()
+    }
+
+    fn query(&self, query: &str) -> Result<String, ParseError> {
+        let id = parsers::parse_line(0, query, preceded(tag("game "), decimal::<usize>))?;
+        let maxima = self
+            .find_maxima(id)
+            .ok_or_else(|| ParseError::new(1, 6, format!("a game from 1 to {}", self.0.len())))?;
+
+        Ok(format!(
+            "{} red, {} green, {} blue, power {}",
+            maxima.red,
+            maxima.green,
+            maxima.blue,
+            maxima.power()
+        ))
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use once_cell::sync::Lazy;
-
-    use super::Solver;
-
-    static TEST_DATA: Lazy<Vec<String>> = Lazy::new(|| {
-        vec![
-            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green".to_string(),
-            "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue".to_string(),
-            "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red".to_string(),
-            "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red".to_string(),
-            "Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green".to_string(),
-        ]
-    });
+    use super::{Games, Solver};
+    use crate::{ParseError, Solution};
 
     #[test]
-    fn test_solve_first() {
-        let solver = Solver::new(TEST_DATA.clone());
-
-        assert_eq!(8, solver.solve_first());
+    fn test_games_match_the_solver() {
+        let data = std::fs::read_to_string("res/examples/day_2_a.txt").unwrap();
+        let data = data.lines().map(str::to_string).collect::<Vec<_>>();
+        let (solver, games) = (Solver::new(data.clone()), Games::new(data));
+
+        assert_eq!(solver.solve_first(), games.solve_first());
+        assert_eq!(solver.solve_second(), games.solve_second());
+        assert_eq!(
+            Ok("20 red, 13 green, 6 blue, power 1560".to_string()),
+            games.query("game 3")
+        );
     }
 
     #[test]
-    fn test_solve_second() {
-        let solver = Solver::new(TEST_DATA.clone());
-
-        assert_eq!(2286, solver.solve_second());
+    fn test_parse_errors() {
+        let error = |line: &str| Solver::try_new(vec![line.to_string()]).err();
+
+        assert_eq!(
+            Some(ParseError::new(1, 1, "\"Game \"")),
+            error("Gaem 1: 3 blue")
+        );
+        assert_eq!(
+            Some(ParseError::new(1, 19, "red, green or blue")),
+            error("Game 1: 3 blue, 4 rde")
+        );
+        assert_eq!(
+            Some(ParseError::new(1, 9, "a number")),
+            error("Game 1: x blue")
+        );
     }
 }
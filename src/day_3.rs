@@ -1,67 +1,50 @@
 use std::collections::HashMap;
 
+use itertools::Itertools;
+
+use crate::{
+    grid::{Grid, Point},
+    parsers::{self, char_grid},
+    render::{Canvas, Cell, Colour, Style},
+    ParseError, Solution,
+};
+
 struct Symbol {
     symbol: char,
-    position: (usize, usize),
+    position: Point,
 }
 
-fn find_symbol(row_idx: usize, col_idx: usize, data: &[Vec<char>]) -> Option<Symbol> {
-    let indices = [
-        (row_idx.checked_sub(1), col_idx.checked_sub(1)),
-        (row_idx.checked_sub(1), Some(col_idx)),
-        (row_idx.checked_sub(1), col_idx.checked_add(1)),
-        (Some(row_idx), col_idx.checked_sub(1)),
-        (Some(row_idx), col_idx.checked_add(1)),
-        (row_idx.checked_add(1), col_idx.checked_sub(1)),
-        (row_idx.checked_add(1), Some(col_idx)),
-        (row_idx.checked_add(1), col_idx.checked_add(1)),
-    ];
-
-    for (row_idx, col_idx) in indices {
-        if let (Some(valid_row), Some(valid_col)) = (row_idx, col_idx) {
-            if let Some(row) = data.get(valid_row) {
-                if let Some(c) = row.get(valid_col) {
-                    if !c.is_ascii_digit() && *c != '.' {
-                        return Some(Symbol {
-                            symbol: *c,
-                            position: (valid_row, valid_col),
-                        });
-                    }
-                }
-            }
-        }
-    }
-
-    None
+fn find_symbol(point: Point, data: &Grid<char>) -> Option<Symbol> {
+    data.adjacent(point)
+        .find(|(_, c)| !c.is_ascii_digit() && **c != '.')
+        .map(|(position, &symbol)| Symbol { symbol, position })
 }
 
 pub struct Solver {
-    data: Vec<Vec<char>>,
+    data: Grid<char>,
 }
 
-impl Solver {
-    #[must_use]
-    pub fn new(data: Vec<String>) -> Self {
-        // Convert lines into grid of characters
-
-        let mut rows = Vec::new();
-        for line in data {
-            let chars = line.chars().collect::<Vec<_>>();
-            rows.push(chars);
-        }
+impl Solution for Solver {
+    type First = u32;
+    type Second = u32;
 
-        Self { data: rows }
+    fn try_new(data: Vec<String>) -> Result<Self, ParseError> {
+        Self::try_from_str(&data.join("\n"))
     }
 
-    #[must_use]
-    pub fn solve_first(&self) -> u32 {
+    fn try_from_str(text: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            data: parsers::parse_all(text, char_grid("a cell"))?,
+        })
+    }
+
+    fn solve_first(&self) -> u32 {
         self.scan_part_numbers()
             .into_iter()
             .fold(0, |acc, (s, v)| acc + if s.is_some() { v } else { 0 })
     }
 
-    #[must_use]
-    pub fn solve_second(&self) -> u32 {
+    fn solve_second(&self) -> u32 {
         // Find all gears
         let gears = self.scan_part_numbers().into_iter().filter_map(|(s, v)| {
             if let Some(Symbol {
@@ -76,7 +59,7 @@ impl Solver {
         });
 
         // Find all part numbers next to gears
-        let mut pairs: HashMap<(usize, usize), (u32, u32)> = HashMap::new();
+        let mut pairs: HashMap<Point, (u32, u32)> = HashMap::new();
         for (position, value) in gears {
             pairs
                 .entry(position)
@@ -87,10 +70,52 @@ impl Solver {
         pairs.values().fold(0, |acc, (v0, v1)| acc + (v0 * v1))
     }
 
+    /// Part numbers in green next to their symbols in yellow, and every other number
+    /// greyed out.
+    fn render(&self) -> Option<Canvas> {
+        let mut canvas = Canvas::new(
+            self.data.width(),
+            self.data
+                .iter()
+                .map(|(_, &c)| match c {
+                    '.' => Cell::new(' ', Style::PLAIN),
+                    _ => Cell::new(c, Style::fg(Colour::GREY)),
+                })
+                .collect(),
+        );
+
+        for (row_idx, row) in self.data.rows().enumerate() {
+            let numbers = row.iter().enumerate().group_by(|(_, c)| c.is_ascii_digit());
+            for (_, number) in numbers.into_iter().filter(|(is_digit, _)| *is_digit) {
+                let points = number
+                    .map(|(col_idx, _)| Point::new(row_idx, col_idx))
+                    .collect_vec();
+                let symbols = points
+                    .iter()
+                    .filter_map(|&point| find_symbol(point, &self.data))
+                    .collect_vec();
+                if symbols.is_empty() {
+                    continue;
+                }
+
+                for point in points {
+                    canvas[point].style = Style::fg(Colour::GREEN);
+                }
+                for symbol in symbols {
+                    canvas[symbol.position].style = Style::fg(Colour::BLACK).on(Colour::YELLOW);
+                }
+            }
+        }
+
+        Some(canvas)
+    }
+}
+
+impl Solver {
     fn scan_part_numbers(&self) -> Vec<(Option<Symbol>, u32)> {
         let mut part_numbers = Vec::new();
 
-        for (row_idx, row) in self.data.iter().enumerate() {
+        for (row_idx, row) in self.data.rows().enumerate() {
             let mut current_part_numbers = Vec::new();
 
             let (mut possible_part_number, mut symbol) = (0, None);
@@ -98,7 +123,7 @@ impl Solver {
             for (col_idx, col_char) in row.iter().enumerate() {
                 if let Some(digit) = col_char.to_digit(10) {
                     possible_part_number = possible_part_number * 10 + digit;
-                    let possible_symbol = find_symbol(row_idx, col_idx, &self.data);
+                    let possible_symbol = find_symbol(Point::new(row_idx, col_idx), &self.data);
                     if possible_symbol.is_some() {
                         symbol = possible_symbol;
                     }
@@ -109,7 +134,7 @@ impl Solver {
                 }
             }
 
-            part_numbers.extend(current_part_numbers.into_iter());
+            part_numbers.extend(current_part_numbers);
         }
 
         part_numbers
@@ -117,39 +142,18 @@ impl Solver {
 }
 
 #[test]
-fn test_solve_first() {
-    let data = vec![
-        "467..114..".to_string(),
-        "...*......".to_string(),
-        "..35..633.".to_string(),
-        "......#...".to_string(),
-        "617*......".to_string(),
-        ".....+.58.".to_string(),
-        "..592.....".to_string(),
-        "......755.".to_string(),
-        "...$.*....".to_string(),
-        ".664.598..".to_string(),
-    ];
-
-    let solver = Solver::new(data);
-    assert_eq!(4361, solver.solve_first());
-}
-
-#[test]
-fn test_solve_second() {
-    let data = vec![
-        "467..114..".to_string(),
-        "...*......".to_string(),
-        "..35..633.".to_string(),
-        "......#...".to_string(),
-        "617*......".to_string(),
-        ".....+.58.".to_string(),
-        "..592.....".to_string(),
-        "......755.".to_string(),
-        "...$.*....".to_string(),
-        ".664.598..".to_string(),
-    ];
-
-    let solver = Solver::new(data);
-    assert_eq!(467_835, solver.solve_second());
+fn test_render() {
+    let data = std::fs::read_to_string("res/examples/day_3_a.txt").unwrap();
+    let canvas = Solver::new(data.lines().map(str::to_string).collect())
+        .render()
+        .unwrap();
+
+    let styled = |row, col| {
+        let cell = canvas[Point::new(row, col)];
+        (cell.glyph, cell.style.foreground)
+    };
+    assert_eq!(('4', Colour::GREEN), styled(0, 0));
+    assert_eq!(('1', Colour::GREY), styled(0, 5));
+    assert_eq!(('*', Colour::BLACK), styled(1, 3));
+    assert_eq!((' ', Colour::WHITE), styled(1, 0));
 }
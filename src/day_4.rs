@@ -1,113 +1,190 @@
+use std::{collections::VecDeque, io::BufRead};
+
+use winnow::{ascii::space1, combinator::separated_pair, stream::Accumulate, PResult, Parser};
+
+use crate::{
+    input::LineReader,
+    parsers::{self, decimal, labelled, number_list, tag},
+    solution::LineSolution,
+    trace::{self, Level},
+    AocError, ParseError, Solution,
+};
+
+/// Both answers, accumulated one card at a time.
+#[derive(Default)]
 pub struct Solver {
-    data: Vec<Card>,
+    points: usize,
+    cards: u32,
+    /// Copies won for the cards after the current one. A card can only win copies of
+    /// the next few cards, so this stays as short as the longest winning list.
+    pending_copies: VecDeque<u32>,
 }
 
+/// The longest list kept without allocating. The puzzle's have 10 and 25 numbers.
+const INLINE_NUMBERS: usize = 32;
+
+/// The numbers of one list in the order they were written. Lists as long as the
+/// puzzle's stay inline, and only longer ones move onto the heap.
+#[derive(Debug)]
+enum Numbers {
+    Inline(usize, [u32; INLINE_NUMBERS]),
+    Spilled(Vec<u32>),
+}
+
+impl Numbers {
+    fn as_slice(&self) -> &[u32] {
+        match self {
+            Self::Inline(len, values) => &values[..*len],
+            Self::Spilled(values) => values,
+        }
+    }
+}
+
+impl Accumulate<u32> for Numbers {
+    fn initial(_capacity: Option<usize>) -> Self {
+        Self::Inline(0, [0; INLINE_NUMBERS])
+    }
+
+    fn accumulate(&mut self, number: u32) {
+        match self {
+            Self::Inline(len, values) if *len < INLINE_NUMBERS => {
+                values[*len] = number;
+                *len += 1;
+            }
+            Self::Inline(_, values) => {
+                let mut spilled = values.to_vec();
+                spilled.push(number);
+                *self = Self::Spilled(spilled);
+            }
+            Self::Spilled(values) => values.push(number),
+        }
+    }
+}
+
+/// The two number lists of a card.
 #[derive(Debug)]
 struct Card {
-    winning_values: Vec<u32>,
-    all_values: Vec<u32>,
+    winning: Numbers,
+    all: Numbers,
 }
 
 impl Card {
+    /// `Card N: ` followed by the winning numbers and then the numbers on the card,
+    /// separated by ` | `.
+    fn parse(input: &mut &str) -> PResult<Self> {
+        let list = number_list::<u32, Numbers>;
+
+        labelled(
+            (tag("Card"), space1, decimal::<u32>),
+            separated_pair(list, tag(" | "), list),
+        )
+        .map(|(_, (winning, all))| Self { winning, all })
+        .parse_next(input)
+    }
+
+    /// How many winning numbers are on the card, counting a repeated winning number
+    /// each time.
     fn get_matches(&self) -> usize {
-        self.winning_values
+        let all = self.all.as_slice();
+        self.winning
+            .as_slice()
             .iter()
-            .filter(|w| self.all_values.contains(w))
+            .filter(|winning| all.contains(winning))
             .count()
     }
 }
 
-impl Solver {
-    #[must_use]
-    pub fn new(data: Vec<String>) -> Self {
-        let mut cards = Vec::new();
-
-        for card_line in data {
-            let Some((_, right)) = card_line.split_once(": ") else {
-                panic!("Splitting card info")
-            };
-
-            let Some((winning, all)) = right.split_once(" | ") else {
-                panic!("Splitting card numbers")
-            };
+impl LineSolution for Solver {
+    #[allow(clippy::cast_possible_truncation)]
+    fn push_line(&mut self, line_idx: usize, card_line: &str) -> Result<(), ParseError> {
+        let matches = parsers::parse_line(line_idx, card_line, Card::parse)?.get_matches();
 
-            let winning = winning
-                .split_whitespace()
-                .filter_map(|v| v.parse::<u32>().ok())
-                .collect::<Vec<_>>();
+        if matches != 0 {
+            self.points += 2_usize.pow(matches.saturating_sub(1) as u32);
+        }
 
-            let all = all
-                .split_whitespace()
-                .filter_map(|v| v.parse::<u32>().ok())
-                .collect::<Vec<_>>();
+        let copies = 1 + self.pending_copies.pop_front().unwrap_or(0);
+        self.cards += copies;
 
-            let card = Card {
-                winning_values: winning,
-                all_values: all,
-            };
+        if self.pending_copies.len() < matches {
+            self.pending_copies.resize(matches, 0);
+        }
+        for pending in self.pending_copies.iter_mut().take(matches) {
+            *pending += copies;
+        }
 
-            cards.push(card);
+        crate::trace!(
+            Level::Summary,
+            "card",
+            card = line_idx + 1,
+            matches = matches,
+            copies = copies
+        );
+        if trace::enabled(Level::Step) {
+            for (offset, pending) in self.pending_copies.iter().take(matches).enumerate() {
+                crate::trace!(
+                    Level::Step,
+                    "copies_won",
+                    card = line_idx + 2 + offset,
+                    won = copies,
+                    pending = *pending
+                );
+            }
         }
 
-        Self { data: cards }
+        Ok(())
     }
+}
 
-    #[must_use]
-    pub fn solve_first(&self) -> usize {
-        self.data
-            .iter()
-            .map(Card::get_matches)
-            .filter(|matches| *matches != 0)
-            .fold(0, |acc, matches| {
-                acc + 2_usize.pow(matches.saturating_sub(1) as u32)
-            })
-    }
+impl Solution for Solver {
+    type First = usize;
+    type Second = u32;
 
-    #[must_use]
-    pub fn solve_second(&self) -> u32 {
-        let total_cards = self.data.len();
+    fn try_new(data: Vec<String>) -> Result<Self, ParseError> {
+        Self::from_strs(data.iter().map(String::as_str))
+    }
 
-        let mut card_copies = vec![1; total_cards];
+    fn try_from_lines<R: BufRead>(lines: LineReader<R>) -> Result<Self, AocError> {
+        Self::stream(lines)
+    }
 
-        for (idx, matches) in self.data.iter().map(Card::get_matches).enumerate() {
-            let num_copies = *card_copies.get(idx).unwrap_or(&1);
-            for offset in 1..=matches {
-                if let Some(c) = card_copies.get_mut(idx + offset) {
-                    *c += num_copies;
-                }
-            }
-        }
+    fn solve_first(&self) -> usize {
+        self.points
+    }
 
-        card_copies.into_iter().sum()
+    fn solve_second(&self) -> u32 {
+        self.cards
     }
 }
 
 #[test]
-fn test_solve_first() {
-    let data = vec![
-        "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53".to_string(),
-        "Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19".to_string(),
-        "Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1".to_string(),
-        "Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83".to_string(),
-        "Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36".to_string(),
-        "Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11".to_string(),
-    ];
-
-    let solver = Solver::new(data);
-    assert_eq!(13, solver.solve_first());
+fn test_parse_errors() {
+    let error = |line: &str| Solver::try_new(vec![line.to_string()]).err();
+
+    assert_eq!(
+        Some(ParseError::new(1, 47, "\" | \"")),
+        error("Card 1: 41 48 83 86 17 83 86  6 31 17  9 48 53")
+    );
+    assert_eq!(
+        Some(ParseError::new(1, 12, "a number")),
+        error("Card 1: 41 4B 83 86 17 | 83 86  6 31 17  9 48 53")
+    );
 }
 
 #[test]
-fn test_solve_second() {
-    let data = vec![
-        "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53".to_string(),
-        "Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19".to_string(),
-        "Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1".to_string(),
-        "Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83".to_string(),
-        "Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36".to_string(),
-        "Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11".to_string(),
-    ];
-
-    let solver = Solver::new(data);
-    assert_eq!(30, solver.solve_second());
+fn test_matches() {
+    let matches = |line: &str| {
+        parsers::parse_line(0, line, Card::parse)
+            .unwrap()
+            .get_matches()
+    };
+
+    assert_eq!(2, matches("Card 1: 5 5 7 | 5 6"));
+    assert_eq!(1, matches("Card 2: 300 1000 | 1000 9"));
+
+    let long = (1..=40)
+        .map(|n| n.to_string())
+        .collect::<Vec<_>>()
+        .join(" ");
+    assert_eq!(40, matches(&format!("Card 3: {long} | {long}")));
 }
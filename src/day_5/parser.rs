@@ -1,91 +1,36 @@
-use once_cell::sync::Lazy;
 use winnow::{
-    ascii::digit1,
-    combinator::{alt, preceded, repeat, separated, separated_pair, terminated},
+    ascii::{digit1, multispace0},
+    combinator::{alt, preceded, separated, separated_pair, terminated},
+    error::{AddContext, ContextError, ErrMode, StrContext, StrContextValue},
+    stream::Stream,
     PResult, Parser,
 };
 
-pub static TEST_DATA: Lazy<Vec<&str>> = Lazy::new(|| {
-    vec![
-        "seeds: 79 14 55 13",
-        "",
-        "seed-to-soil map:",
-        "50 98 2",
-        "52 50 48",
-        "",
-        "soil-to-fertilizer map:",
-        "0 15 37",
-        "37 52 2",
-        "39 0 15",
-        "",
-        "fertilizer-to-water map:",
-        "49 53 8",
-        "0 11 42",
-        "42 0 7",
-        "57 7 4",
-        "",
-        "water-to-light map:",
-        "88 18 7",
-        "18 25 70",
-        "",
-        "light-to-temperature map:",
-        "45 77 23",
-        "81 45 19",
-        "68 64 13",
-        "",
-        "temperature-to-humidity map:",
-        "0 69 1",
-        "1 0 69",
-        "",
-        "humidity-to-location map:",
-        "60 56 37",
-        "56 93 4",
-        "",
-    ]
-});
-
-pub static TEST_DATA_STR: &str = r#"seeds: 79 14 55 13
-
-seed-to-soil map:
-50 98 2
-52 50 48
-
-soil-to-fertilizer map:
-0 15 37
-37 52 2
-39 0 15
-
-fertilizer-to-water map:
-49 53 8
-0 11 42
-42 0 7
-57 7 4
-
-water-to-light map:
-88 18 7
-18 25 70
-
-light-to-temperature map:
-45 77 23
-81 45 19
-68 64 13
-
-temperature-to-humidity map:
-0 69 1
-1 0 69
-
-humidity-to-location map:
-60 56 37
-56 93 4"#;
-
 use super::{Layer, Mapping};
+use crate::{
+    interval::Offset,
+    parsers::{lines, sections},
+};
+
+pub(super) fn parse_almanac(input: &mut &str) -> PResult<(Vec<u64>, Vec<Layer>)> {
+    terminated((parse_seeds, parse_all_maps), multispace0).parse_next(input)
+}
 
 pub(super) fn parse_seeds(input: &mut &str) -> PResult<Vec<u64>> {
-    terminated(preceded("seeds: ", parse_list), "\n\n").parse_next(input)
+    terminated(
+        preceded(
+            "seeds: ".context(StrContext::Expected(StrContextValue::StringLiteral(
+                "seeds: ",
+            ))),
+            parse_list,
+        ),
+        "\n\n",
+    )
+    .parse_next(input)
 }
 
 pub(super) fn parse_all_maps(input: &mut &str) -> PResult<Vec<Layer>> {
-    separated(1.., parse_map, "\n").parse_next(input)
+    sections(parse_map).parse_next(input)
 }
 
 fn parse_digits(input: &mut &str) -> PResult<u64> {
@@ -93,23 +38,42 @@ fn parse_digits(input: &mut &str) -> PResult<u64> {
 }
 
 fn parse_map_entry(input: &mut &str) -> PResult<Mapping> {
-    terminated(parse_list, "\n")
+    parse_list
         .verify(|values: &[u64]| values.len() == 3)
         .map(|values: Vec<u64>| Mapping {
             destination: values[0],
             source: values[1],
             range: values[2],
         })
+        .context(StrContext::Expected(StrContextValue::Description(
+            "three numbers",
+        )))
         .parse_next(input)
 }
 
+/// A map entry that moves every number to somewhere below `2^64`.
+fn parse_offset(input: &mut &str) -> PResult<Offset> {
+    let start = input.checkpoint();
+    let mapping = parse_map_entry(input)?;
+
+    Offset::try_from(mapping).map_err(|()| {
+        input.reset(start);
+        ErrMode::Cut(ContextError::new().add_context(
+            input,
+            StrContext::Expected(StrContextValue::Description(
+                "a mapping that stays below 2^64",
+            )),
+        ))
+    })
+}
+
 fn parse_list(input: &mut &str) -> PResult<Vec<u64>> {
     separated(1.., parse_digits, ' ').parse_next(input)
 }
 
 fn parse_map(input: &mut &str) -> PResult<Layer> {
-    preceded(parse_mapping_header, repeat(1.., parse_map_entry))
-        .map(|maps| Layer(maps))
+    preceded(parse_mapping_header, lines(parse_offset))
+        .map(|offsets: Vec<Offset>| Layer(offsets.into_iter().collect()))
         .parse_next(input)
 }
 
@@ -132,12 +96,15 @@ fn parse_mapping_header<'s>(input: &mut &'s str) -> PResult<(&'s str, &'s str)>
         separated_pair(parse_map_label, "-to-", parse_map_label),
         " map:\n",
     )
+    .context(StrContext::Expected(StrContextValue::Description(
+        "a map header",
+    )))
     .parse_next(input)
 }
 
 #[test]
 fn test_winnow_parsing() {
-    let mut test_data = TEST_DATA_STR;
+    let mut test_data = include_str!("../../res/examples/day_5_a.txt");
 
     let seeds = parse_seeds.parse_next(&mut test_data).unwrap();
 
@@ -147,15 +114,13 @@ fn test_winnow_parsing() {
 
     assert_eq!(7, all_mappings.len());
     assert_eq!(
-        Mapping {
-            destination: 60,
-            source: 56,
-            range: 37
-        },
-        all_mappings
-            .last()
-            .and_then(|last| last.0.last())
-            .unwrap()
-            .clone()
+        Offset::try_from(Mapping {
+            destination: 56,
+            source: 93,
+            range: 4
+        })
+        .ok()
+        .as_ref(),
+        all_mappings.last().and_then(|last| last.0.pieces().last())
     );
 }
@@ -1,60 +1,74 @@
-use std::num::ParseIntError;
+use winnow::Parser;
 
-use itertools::Itertools;
+use crate::{
+    parsers::{self, labelled, number_list, tag},
+    ParseError, Solution,
+};
 
 pub struct Solver {
-    data: Vec<String>,
+    times: Vec<u64>,
+    distances: Vec<u64>,
 }
 
-impl Solver {
-    #[must_use]
-    pub fn new(data: Vec<String>) -> Self {
-        assert_eq!(2, data.len());
-        Self { data }
-    }
+impl Solution for Solver {
+    type First = u64;
+    type Second = u64;
+
+    fn try_new(data: Vec<String>) -> Result<Self, ParseError> {
+        let list = |line_idx: usize, label| {
+            let line = data
+                .get(line_idx)
+                .ok_or_else(|| ParseError::new(line_idx + 1, 1, format!("\"{label}:\"")))?;
+            parsers::parse_line(
+                line_idx,
+                line,
+                labelled(tag(label), number_list::<u64, Vec<_>>).map(|(_, values)| values),
+            )
+        };
+        let times = list(0, "Time")?;
+        let distances = list(1, "Distance")?;
 
-    #[must_use]
-    pub fn solve_first(&self) -> u64 {
-        let times: Vec<u64> = self
-            .transform_list(0, |x| x.split_whitespace().map(str::parse).collect())
-            .unwrap();
-        let distances: Vec<u64> = self
-            .transform_list(1, |x| x.split_whitespace().map(str::parse).collect())
-            .unwrap();
+        if times.len() != distances.len() {
+            return Err(ParseError::new(
+                2,
+                data[1].chars().count() + 1,
+                format!("{} distances", times.len()),
+            ));
+        }
+        if data.len() > 2 {
+            return Err(ParseError::new(3, 1, "end of input"));
+        }
 
-        times
+        Ok(Self { times, distances })
+    }
+
+    fn solve_first(&self) -> u64 {
+        self.times
             .iter()
-            .zip(&distances)
+            .zip(&self.distances)
             .fold(1, |acc, (&time, &distance)| {
                 acc * run_boat_attempts(time, distance)
             })
     }
 
-    #[must_use]
-    pub fn solve_second(&self) -> u64 {
-        let time = self
-            .transform_list(0, |x| x.split_whitespace().join("").parse())
-            .unwrap();
-        let distance = self
-            .transform_list(1, |x| x.split_whitespace().join("").parse())
-            .unwrap();
-
-        run_boat_attempts(time, distance)
+    fn solve_second(&self) -> u64 {
+        run_boat_attempts(concatenate(&self.times), concatenate(&self.distances))
     }
+}
 
-    fn transform_list<T>(
-        &self,
-        index: usize,
-        transform: fn(&str) -> Result<T, ParseIntError>,
-    ) -> Result<T, ParseIntError> {
-        let label = if index == 0 { "Time:" } else { "Distance:" };
-        self.data[index]
-            .strip_prefix(label)
-            .ok_or_else(|| panic!("stripping {label} prefix"))
-            .and_then(transform)
-    }
+/// Reads the numbers as the digits of one number, ignoring the spaces between them.
+fn concatenate(numbers: &[u64]) -> u64 {
+    numbers.iter().fold(0, |acc, &n| {
+        let digits = n.checked_ilog10().map_or(1, |log| log + 1);
+        acc * 10_u64.pow(digits) + n
+    })
 }
 
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
 fn run_boat_attempts(max_time: u64, min_distance: u64) -> u64 {
     let det = ((max_time.pow(2) - 4 * min_distance) as f64).sqrt();
 
@@ -66,9 +80,19 @@ fn run_boat_attempts(max_time: u64, min_distance: u64) -> u64 {
 
 #[cfg(test)]
 mod tests {
-    use super::run_boat_attempts;
+    use super::{run_boat_attempts, Solver};
+    use crate::{ParseError, Solution};
     use rstest::rstest;
 
+    #[rstest]
+    #[case(vec!["Time: 7 15"], ParseError::new(2, 1, "\"Distance:\""))]
+    #[case(vec!["Time: 7 15", "Distance: 9"], ParseError::new(2, 12, "2 distances"))]
+    #[case(vec!["Time: 7 1S", "Distance: 9 40"], ParseError::new(1, 9, "a number"))]
+    fn parse_errors(#[case] input: Vec<&str>, #[case] expected: ParseError) {
+        let input = input.into_iter().map(str::to_string).collect();
+        assert_eq!(Some(expected), Solver::try_new(input).err());
+    }
+
     #[rstest]
     #[case((7, 9), 4)]
     #[case((15, 40), 8)]
@@ -1,4 +1,17 @@
-use std::{cmp::Ordering, collections::HashMap};
+use std::{cmp::Ordering, collections::HashMap, io::BufRead};
+
+use winnow::{
+    combinator::{preceded, separated_pair},
+    token::any,
+    PResult, Parser,
+};
+
+use crate::{
+    input::LineReader,
+    parsers::{self, decimal, tag},
+    solution::LineSolution,
+    AocError, ParseError, Solution,
+};
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum Card {
@@ -102,7 +115,6 @@ impl TryFrom<char> for Card {
 #[derive(Clone, PartialEq, Eq)]
 pub struct Hand {
     cards: [Card; 5],
-    bid: u64,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -116,16 +128,30 @@ pub enum Strength {
     Five,
 }
 
+impl Strength {
+    const fn name(self) -> &'static str {
+        match self {
+            Self::High => "high card",
+            Self::One => "one pair",
+            Self::Two => "two pair",
+            Self::Three => "three of a kind",
+            Self::Full => "full house",
+            Self::Four => "four of a kind",
+            Self::Five => "five of a kind",
+        }
+    }
+}
+
 impl Hand {
-    const fn new(cards: [Card; 5], bid: u64) -> Self {
-        Self { cards, bid }
+    const fn new(cards: [Card; 5]) -> Self {
+        Self { cards }
     }
 
     fn strength(&self) -> Strength {
         let card_matches = self.count_card_matches();
 
-        let unique_cards = card_matches.len();
-        let highest_match = card_matches.values().max().unwrap();
+        let unique_cards = card_matches.iter().filter(|&&count| count > 0).count();
+        let highest_match = *card_matches.iter().max().unwrap();
 
         match (unique_cards, highest_match) {
             (1, _) => Strength::Five,
@@ -142,9 +168,9 @@ impl Hand {
     fn strength_jack(&self) -> Strength {
         let card_matches = self.count_card_matches();
 
-        let unique_cards = card_matches.len();
-        let highest_match = card_matches.values().max().unwrap();
-        let num_jacks = card_matches.get(&11);
+        let unique_cards = card_matches.iter().filter(|&&count| count > 0).count();
+        let highest_match = *card_matches.iter().max().unwrap();
+        let num_jacks = Some(card_matches[usize::from(Card::Jack.value())]).filter(|&n| n > 0);
 
         match (unique_cards, highest_match, num_jacks) {
             (1, _, _) | (2, _, Some(_)) => Strength::Five,
@@ -158,14 +184,12 @@ impl Hand {
         }
     }
 
-    fn count_card_matches(&self) -> HashMap<u8, i32> {
-        let mut card_matches = HashMap::new();
+    /// How many of each card the hand holds, indexed by [`Card::value`].
+    fn count_card_matches(&self) -> [u8; 14] {
+        let mut card_matches = [0; 14];
 
         for card in self.cards {
-            card_matches
-                .entry(card.value())
-                .and_modify(|c| *c += 1)
-                .or_insert(1);
+            card_matches[usize::from(card.value())] += 1;
         }
 
         card_matches
@@ -200,74 +224,126 @@ impl Hand {
     }
 }
 
+/// The bids placed on one distinct hand, in input order. Identical hands take
+/// consecutive ranks in the order they were dealt, so the winnings of a group ranked
+/// from `r` are `r * bids + weighted_bids`.
+#[derive(Default)]
+struct Tally {
+    count: u64,
+    bids: u64,
+    /// Each bid times the number of identical hands dealt before it.
+    weighted_bids: u64,
+}
+
+/// One tally per distinct hand. There are at most 13^5 of those, however many
+/// hands are dealt.
+#[derive(Default)]
 pub struct Solver {
-    hands: Vec<Hand>,
+    hands: HashMap<[u8; 5], (Hand, Tally)>,
 }
 
 impl Solver {
-    #[must_use]
-    pub fn new(data: Vec<String>) -> Self {
-        let mut hands = Vec::new();
-
-        for line in data {
-            let (cards_str, bid) = line.split_once(' ').unwrap();
-            let mut cards = [Card::Ace; 5];
-            for (idx, card) in cards_str.chars().enumerate() {
-                cards[idx] = Card::try_from(card).unwrap();
-            }
-            let bid = bid.parse().unwrap();
-            let hand = Hand::new(cards, bid);
-            hands.push(hand);
+    fn winnings(&self, cmp: fn(&Hand, &Hand) -> Ordering) -> u64 {
+        let mut hands = self.hands.values().collect::<Vec<_>>();
+        hands.sort_by(|(a, _), (b, _)| cmp(a, b));
+
+        let mut rank = 1;
+        let mut total = 0;
+        for (_, tally) in hands {
+            total += rank * tally.bids + tally.weighted_bids;
+            rank += tally.count;
         }
+        total
+    }
+}
 
-        Self { hands }
+impl LineSolution for Solver {
+    fn push_line(&mut self, line_idx: usize, line: &str) -> Result<(), ParseError> {
+        let (cards, bid) = parsers::parse_line(line_idx, line, hand_and_bid)?;
+
+        let (_, tally) = self
+            .hands
+            .entry(cards.map(Card::value))
+            .or_insert_with(|| (Hand::new(cards), Tally::default()));
+        tally.weighted_bids += tally.count * bid;
+        tally.bids += bid;
+        tally.count += 1;
+
+        Ok(())
     }
+}
 
-    #[must_use]
-    pub fn solve_first(&self) -> u64 {
-        let mut hands = self.hands.clone();
-        hands.sort_by(Hand::cmp);
-        hands
-            .into_iter()
-            .enumerate()
-            .fold(0, |acc, (idx, hand)| acc + (hand.bid * (idx as u64 + 1)))
+/// Five cards, a space and the bid.
+fn hand_and_bid(input: &mut &str) -> PResult<([Card; 5], u64)> {
+    separated_pair(cards, tag(" "), decimal).parse_next(input)
+}
+
+fn cards(input: &mut &str) -> PResult<[Card; 5]> {
+    (card, card, card, card, card)
+        .map(<[Card; 5]>::from)
+        .parse_next(input)
+}
+
+fn card(input: &mut &str) -> PResult<Card> {
+    any.verify_map(|c| Card::try_from(c).ok())
+        .context(parsers::expected("a card"))
+        .parse_next(input)
+}
+
+impl Solution for Solver {
+    type First = u64;
+    type Second = u64;
+
+    const QUERIES: &'static [(&'static str, &'static str)] = &[(
+        "hand CARDS",
+        "the type of a hand, with and without jokers, and how often it was dealt",
+    )];
+
+    fn try_new(data: Vec<String>) -> Result<Self, ParseError> {
+        Self::from_strs(data.iter().map(String::as_str))
     }
 
-    #[must_use]
-    pub fn solve_second(&self) -> u64 {
-        let mut hands = self.hands.clone();
-        hands.sort_by(Hand::cmp_jack);
-        hands
-            .into_iter()
-            .enumerate()
-            .fold(0, |acc, (idx, hand)| acc + (hand.bid * (idx as u64 + 1)))
+    fn try_from_lines<R: BufRead>(lines: LineReader<R>) -> Result<Self, AocError> {
+        Self::stream(lines)
+    }
+
+    fn solve_first(&self) -> u64 {
+        self.winnings(Hand::cmp)
+    }
+
+    fn solve_second(&self) -> u64 {
+        self.winnings(Hand::cmp_jack)
+    }
+
+    fn query(&self, query: &str) -> Result<String, ParseError> {
+        let cards = parsers::parse_line(0, query, preceded(tag("hand "), cards))?;
+        let hand = Hand::new(cards);
+
+        let dealt = self
+            .hands
+            .get(&cards.map(Card::value))
+            .map_or(0, |(_, tally)| tally.count);
+        Ok(format!(
+            "{}, {} with jokers, dealt {dealt} times",
+            hand.strength().name(),
+            hand.strength_jack().name()
+        ))
     }
 }
 
 #[test]
-pub fn test_solve_first() {
-    let data = vec![
-        "32T3K 765".to_string(),
-        "T55J5 684".to_string(),
-        "KK677 28".to_string(),
-        "KTJJT 220".to_string(),
-        "QQQJA 483".to_string(),
-    ];
-
-    let solver = Solver::new(data);
-    assert_eq!(6440, solver.solve_first());
+pub fn test_identical_hands_rank_in_input_order() {
+    let solver = Solver::try_from_str("32T3K 765\nKK677 28\n32T3K 10").unwrap();
+
+    assert_eq!(765 + 10 * 2 + 28 * 3, solver.solve_first());
 }
 
 #[test]
-pub fn test_solve_second() {
-    let data = vec![
-        "32T3K 765".to_string(),
-        "T55J5 684".to_string(),
-        "KK677 28".to_string(),
-        "KTJJT 220".to_string(),
-        "QQQJA 483".to_string(),
-    ];
-
-    let solver = Solver::new(data);
-    assert_eq!(5905, solver.solve_second());
+pub fn test_parse_errors() {
+    let error = |line: &str| Solver::try_new(vec![line.to_string()]).err();
+
+    assert_eq!(Some(ParseError::new(1, 3, "a card")), error("32X3K 765"));
+    assert_eq!(Some(ParseError::new(1, 5, "a card")), error("32T3 765"));
+    assert_eq!(Some(ParseError::new(1, 6, "\" \"")), error("32T3KK 765"));
+    assert_eq!(Some(ParseError::new(1, 7, "a number")), error("32T3K -765"));
 }
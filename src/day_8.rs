@@ -1,6 +1,19 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 
-use itertools::Itertools;
+use winnow::{
+    ascii::alphanumeric1,
+    ascii::line_ending,
+    combinator::{alt, peek, preceded, repeat, separated_pair, terminated},
+    PResult, Parser,
+};
+
+use crate::{
+    math,
+    parsers::{self, blank_line, lines, node_line, tag},
+    solution::DynSolution,
+    trace::{self, Level},
+    ParseError, Solution,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Direction {
@@ -8,60 +21,199 @@ pub enum Direction {
     Right,
 }
 
+/// One `AAA = (BBB, CCC)` line, borrowed from the input.
+struct Node<'a> {
+    name: &'a str,
+    left: &'a str,
+    right: &'a str,
+}
+
+/// The directions, an empty line and then one node per line.
+fn network<'a>(input: &mut &'a str) -> PResult<(Vec<Direction>, Vec<Node<'a>>)> {
+    let directions = terminated(
+        repeat(1.., direction),
+        peek(line_ending).context(parsers::expected("L or R")),
+    );
+    let node = node_line.map(|(name, (left, right))| Node { name, left, right });
+
+    separated_pair(directions, blank_line, lines(node)).parse_next(input)
+}
+
+fn direction(input: &mut &str) -> PResult<Direction> {
+    alt(('L'.value(Direction::Left), 'R'.value(Direction::Right))).parse_next(input)
+}
+
+/// The network with every node name replaced by an index, so solving never touches
+/// the input text.
 pub struct Solver {
     order: Vec<Direction>,
-    nodes: HashMap<String, (String, String)>,
+    /// The left and right neighbours of each node, or `None` for a node that is only
+    /// ever referred to.
+    edges: Vec<Option<(usize, usize)>>,
+    /// Whether each node's name ends with `Z`.
+    is_end: Vec<bool>,
+    first_start: Option<usize>,
+    first_end: Option<usize>,
+    /// The nodes with a line of their own whose name ends with `A`, in the order
+    /// they first show up in.
+    starts: Vec<usize>,
+    /// Only kept for `--explain` and the REPL, which are the only ones to show them.
+    names: Option<Names>,
+}
+
+/// The node names behind the indices.
+struct Names {
+    indices: HashMap<String, usize>,
+    names: Vec<String>,
 }
 
 impl Solver {
-    #[must_use]
-    pub fn new(data: Vec<String>) -> Self {
-        let order = data[0].clone();
-        let order = order
-            .chars()
-            .map(|c| {
-                if c == 'L' {
-                    Direction::Left
-                } else {
-                    Direction::Right
-                }
-            })
-            .collect();
-        let mut nodes = HashMap::new();
-        for node_line in &data[2..] {
-            let (start, nodes_str) = node_line.split_once(" = ").unwrap();
-            let (left, right) = nodes_str
-                .strip_prefix('(')
-                .and_then(|x| x.strip_suffix(')'))
-                .and_then(|x| x.split(", ").collect_tuple::<(&str, &str)>())
-                .unwrap();
-
-            nodes
-                .entry(start.to_string())
-                .or_insert((left.to_string(), right.to_string()));
+    fn parse<'a>(text: &'a str, keep_names: bool) -> Result<Self, ParseError> {
+        let (order, nodes) = parsers::parse_all(text, network)?;
+
+        let mut indices = HashMap::<&'a str, usize>::new();
+        let mut index = |name: &'a str| {
+            let next = indices.len();
+            *indices.entry(name).or_insert(next)
+        };
+
+        let mut edges = Vec::new();
+        for node in nodes {
+            let (name, left, right) = (index(node.name), index(node.left), index(node.right));
+
+            edges.resize(edges.len().max(name.max(left).max(right) + 1), None);
+            edges[name].get_or_insert((left, right));
         }
-        Self { order, nodes }
+        edges.resize(indices.len(), None);
+
+        let mut is_end = vec![false; indices.len()];
+        let mut starts = Vec::new();
+        for (name, &node) in &indices {
+            is_end[node] = name.ends_with('Z');
+            if edges[node].is_some() && name.ends_with('A') {
+                starts.push(node);
+            }
+        }
+        starts.sort_unstable();
+
+        Ok(Self {
+            order,
+            edges,
+            is_end,
+            first_start: indices.get("AAA").copied(),
+            first_end: indices.get("ZZZ").copied(),
+            starts,
+            names: keep_names.then(|| {
+                let mut names = vec![String::new(); indices.len()];
+                for (&name, &node) in &indices {
+                    names[node] = name.to_string();
+                }
+                Names {
+                    indices: indices
+                        .into_iter()
+                        .map(|(name, node)| (name.to_string(), node))
+                        .collect(),
+                    names,
+                }
+            }),
+        })
     }
+}
 
-    #[must_use]
-    pub fn solve_first(&self) -> u64 {
-        self.count_steps("AAA", |node| node == "ZZZ")
+impl Solution for Solver {
+    type First = u64;
+    type Second = u64;
+
+    const QUERIES: &'static [(&'static str, &'static str)] = &[(
+        "next NODE DIRECTIONS",
+        "the nodes visited from NODE following DIRECTIONS, such as LLR",
+    )];
+
+    fn try_new(data: Vec<String>) -> Result<Self, ParseError> {
+        Self::try_from_str(&data.join("\n"))
+    }
+
+    fn try_from_str(text: &str) -> Result<Self, ParseError> {
+        Self::parse(text, trace::enabled(Level::Summary))
     }
 
-    #[must_use]
-    pub fn solve_second(&self) -> u64 {
+    fn try_new_model(data: Vec<String>) -> Result<Box<dyn DynSolution>, ParseError> {
+        Ok(Box::new(Self::parse(&data.join("\n"), true)?))
+    }
+
+    fn solve_first(&self) -> u64 {
+        self.count_steps(self.first_start.unwrap(), |node| {
+            Some(node) == self.first_end
+        })
+    }
+
+    fn solve_second(&self) -> u64 {
         let node_steps = self
-            .nodes
-            .keys()
-            .filter(|node| node.ends_with('A'))
-            .map(|node| self.count_steps(node, |node| node.ends_with('Z')));
+            .starts
+            .iter()
+            .map(|&node| self.count_steps(node, |node| self.is_end[node]));
+
+        if !trace::enabled(Level::Summary) {
+            return math::lcm(node_steps).expect("the answer fits in a u64");
+        }
+        let node_steps = node_steps.collect::<Vec<_>>();
+        let steps = math::lcm(node_steps.iter().copied()).expect("the answer fits in a u64");
+        crate::trace!(Level::Summary, "lcm", cycles = node_steps, steps = steps);
+        steps
+    }
+
+    fn query(&self, query: &str) -> Result<String, ParseError> {
+        let (name, directions) = parsers::parse_line(
+            0,
+            query,
+            preceded(
+                tag("next "),
+                separated_pair(
+                    alphanumeric1,
+                    tag(" "),
+                    repeat::<_, _, Vec<_>, _, _>(
+                        1..,
+                        direction.context(parsers::expected("L or R")),
+                    ),
+                ),
+            ),
+        )?;
+        let mut node = self
+            .node(name)
+            .filter(|&node| self.edges[node].is_some())
+            .ok_or_else(|| ParseError::at(0, query, name, "a node with a line of its own"))?;
+
+        let mut path = name.to_string();
+        for direction in directions {
+            node = self.next_node(node, direction);
+            path.push_str(match direction {
+                Direction::Left => " -L-> ",
+                Direction::Right => " -R-> ",
+            });
+            path.push_str(self.name(node));
+            if self.edges[node].is_none() {
+                break;
+            }
+        }
+        Ok(path)
+    }
+}
+
+impl Solver {
+    /// The node called `name`, if the names were kept.
+    fn node(&self, name: &str) -> Option<usize> {
+        self.names.as_ref()?.indices.get(name).copied()
+    }
 
-        lcm(node_steps)
+    /// The name of `node`, or `?` if the names weren't kept.
+    fn name(&self, node: usize) -> &str {
+        self.names
+            .as_ref()
+            .map_or("?", |names| names.names[node].as_str())
     }
 
-    fn next_node(&self, current_node: &str, direction: Direction) -> &String {
-        self.nodes
-            .get(current_node)
+    fn next_node(&self, current_node: usize, direction: Direction) -> usize {
+        self.edges[current_node]
             .map(|(left, right)| match direction {
                 Direction::Left => left,
                 Direction::Right => right,
@@ -69,7 +221,7 @@ impl Solver {
             .unwrap()
     }
 
-    fn count_steps(&self, initial_node: &str, node_check: fn(&str) -> bool) -> u64 {
+    fn count_steps(&self, initial_node: usize, node_check: impl Fn(usize) -> bool) -> u64 {
         let mut current_node = initial_node;
 
         let mut counter = 0;
@@ -77,78 +229,104 @@ impl Solver {
         for direction in self.order.iter().cycle() {
             current_node = self.next_node(current_node, *direction);
             counter += 1;
+            crate::trace!(
+                Level::Detail,
+                "visit",
+                step = counter,
+                direction = direction,
+                node = self.name(current_node)
+            );
             if node_check(current_node) {
                 break;
             }
         }
 
+        crate::trace!(
+            Level::Summary,
+            "walk",
+            from = self.name(initial_node),
+            to = self.name(current_node),
+            steps = counter
+        );
         counter
     }
 }
 
-fn factorize(mut n: u64) -> HashSet<u64> {
-    let mut factors = HashSet::new();
-    while n > 0 && n % 2 == 0 {
-        factors.insert(2);
-        n /= 2;
-    }
-
-    for factor in (3..=n).step_by(2) {
-        while n % factor == 0 {
-            factors.insert(factor);
-            n /= factor;
-            if n == 0 {
-                break;
-            }
-        }
-    }
+#[test]
+pub fn test_parse_errors() {
+    let error =
+        |lines: &[&str]| Solver::try_new(lines.iter().map(ToString::to_string).collect()).err();
 
-    factors
+    assert_eq!(
+        Some(ParseError::new(1, 2, "L or R")),
+        error(&["LX", "", "AAA = (BBB, CCC)"])
+    );
+    assert_eq!(
+        Some(ParseError::new(3, 4, "\" = \"")),
+        error(&["LR", "", "AAA - (BBB, CCC)"])
+    );
+    assert_eq!(
+        Some(ParseError::new(3, 16, "\")\"")),
+        error(&["LR", "", "AAA = (BBB, CCC"])
+    );
 }
 
-fn lcm(numbers: impl Iterator<Item = u64>) -> u64 {
-    numbers
-        .map(factorize)
-        .fold(HashSet::new(), |acc, set| {
-            acc.union(&set).copied().collect()
-        })
-        .into_iter()
-        .product()
+#[test]
+pub fn test_cycles_sharing_prime_factors() {
+    let solver = Solver::try_from_str(
+        "L
+
+11A = (11B, XXX)
+11B = (11C, XXX)
+11C = (11D, XXX)
+11D = (11Z, XXX)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, XXX)
+22C = (22D, XXX)
+22D = (22E, XXX)
+22E = (22F, XXX)
+22F = (22Z, XXX)
+22Z = (22B, XXX)
+XXX = (XXX, XXX)",
+    )
+    .unwrap();
+
+    assert_eq!(12, solver.solve_second());
 }
 
 #[test]
-pub fn test_solve_first() {
-    let data = vec![
-        "RL".to_string(),
-        String::new(),
-        "AAA = (BBB, CCC)".to_string(),
-        "BBB = (DDD, EEE)".to_string(),
-        "CCC = (ZZZ, GGG)".to_string(),
-        "DDD = (DDD, DDD)".to_string(),
-        "EEE = (EEE, EEE)".to_string(),
-        "GGG = (GGG, GGG)".to_string(),
-        "ZZZ = (ZZZ, ZZZ)".to_string(),
-    ];
-
-    let solver = Solver::new(data);
-    assert_eq!(2, solver.solve_first());
+pub fn test_names_are_only_kept_to_show_them() {
+    let text = "L\n\nAAA = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)";
+
+    assert!(Solver::try_from_str(text).unwrap().names.is_none());
+
+    let (solver, events) = trace::record(Some(Level::Summary), || {
+        let solver = Solver::try_from_str(text).unwrap();
+        solver.solve_first();
+        solver
+    });
+    assert_eq!(Some(0), solver.node("AAA"));
+    assert_eq!(
+        "walk from=\"AAA\" to=\"ZZZ\" steps=1",
+        events[0].to_string()
+    );
 }
 
 #[test]
-pub fn test_solve_second() {
-    let data = vec![
-        "LR".to_string(),
-        String::new(),
-        "11A = (11B, XXX)".to_string(),
-        "11B = (XXX, 11Z)".to_string(),
-        "11Z = (11B, XXX)".to_string(),
-        "22A = (22B, XXX)".to_string(),
-        "22B = (22C, 22C)".to_string(),
-        "22C = (22Z, 22Z)".to_string(),
-        "22Z = (22B, 22B)".to_string(),
-        "XXX = (XXX, XXX)".to_string(),
-    ];
-
-    let solver = Solver::new(data);
-    assert_eq!(6, solver.solve_second());
+pub fn test_starts_are_walked_in_input_order() {
+    let solver = Solver::parse(
+        "L\n\nDDA = (DDZ, DDZ)\nBBA = (BBZ, BBZ)\nEEA = (EEZ, EEZ)\nAAA = (AAZ, AAZ)\nCCA = (CCZ, CCZ)",
+        true,
+    )
+    .unwrap();
+
+    assert_eq!(
+        vec!["DDA", "BBA", "EEA", "AAA", "CCA"],
+        solver
+            .starts
+            .iter()
+            .map(|&node| solver.name(node))
+            .collect::<Vec<_>>()
+    );
 }
@@ -1,40 +1,71 @@
+use std::io::BufRead;
+
 use itertools::Itertools;
 
+use crate::{
+    input::LineReader, parsers, solution::LineSolution, trace::Level, AocError, ParseError,
+    Solution,
+};
+
+/// Sums of the extrapolated values, accumulated one history at a time.
+#[derive(Default)]
 pub struct Solver {
-    history: Vec<Vec<i64>>,
+    next_sum: i64,
+    previous_sum: i64,
 }
 
-impl Solver {
-    #[must_use]
-    pub fn new(data: Vec<String>) -> Self {
-        let history = data
-            .into_iter()
-            .map(|line| {
-                line.split_whitespace()
-                    .filter_map(|c| c.parse().ok())
-                    .collect()
-            })
-            .collect();
-
-        Self { history }
+impl LineSolution for Solver {
+    fn push_line(&mut self, line_idx: usize, line: &str) -> Result<(), ParseError> {
+        let values = parsers::numbers(line_idx, line, line)?;
+        if values.is_empty() {
+            return Err(ParseError::new(line_idx + 1, 1, "a number"));
+        }
+
+        let history = build_history(&values);
+
+        // The pyramid is built from the constant row up, so it's reported top down.
+        for (depth, row) in history.iter().rev().enumerate() {
+            crate::trace!(Level::Step, "differences", depth = depth, row = row);
+        }
+
+        if let Some(extrapolated) = history.last() {
+            let (next, previous) = (
+                extrapolated.last().copied().unwrap_or_default(),
+                extrapolated.first().copied().unwrap_or_default(),
+            );
+            crate::trace!(
+                Level::Summary,
+                "history",
+                line = line_idx + 1,
+                next = next,
+                previous = previous
+            );
+            self.next_sum += next;
+            self.previous_sum += previous;
+        }
+
+        Ok(())
+    }
+}
+
+impl Solution for Solver {
+    type First = i64;
+    type Second = i64;
+
+    fn try_new(data: Vec<String>) -> Result<Self, ParseError> {
+        Self::from_strs(data.iter().map(String::as_str))
     }
 
-    #[must_use]
-    pub fn solve_first(&self) -> i64 {
-        self.history
-            .iter()
-            .map(|line| build_history(line))
-            .filter_map(|history| history.last().and_then(|line| line.last()).copied())
-            .sum()
+    fn try_from_lines<R: BufRead>(lines: LineReader<R>) -> Result<Self, AocError> {
+        Self::stream(lines)
     }
 
-    #[must_use]
-    pub fn solve_second(&self) -> i64 {
-        self.history
-            .iter()
-            .map(|line| build_history(line))
-            .filter_map(|history| history.last().and_then(|line| line.first()).copied())
-            .sum()
+    fn solve_first(&self) -> i64 {
+        self.next_sum
+    }
+
+    fn solve_second(&self) -> i64 {
+        self.previous_sum
     }
 }
 
@@ -77,25 +108,15 @@ fn back_propagate(current_line_idx: usize, history: &mut Vec<Vec<i64>>) {
 }
 
 #[test]
-pub fn test_solve_first() {
-    let data = vec![
-        "0 3 6 9 12 15".to_string(),
-        "1 3 6 10 15 21".to_string(),
-        "10 13 16 21 30 45".to_string(),
-    ];
-
-    let solver = Solver::new(data);
-    assert_eq!(114, solver.solve_first());
-}
+pub fn test_parse_errors() {
+    let data = vec!["0 3 6 9 12 15".to_string(), "1 3 6 1O 15 21".to_string()];
 
-#[test]
-pub fn test_solve_second() {
-    let data = vec![
-        "0 3 6 9 12 15".to_string(),
-        "1 3 6 10 15 21".to_string(),
-        "10 13 16 21 30 45".to_string(),
-    ];
-
-    let solver = Solver::new(data);
-    assert_eq!(2, solver.solve_second());
+    assert_eq!(
+        Some(ParseError::new(2, 7, "a number")),
+        Solver::try_new(data).err()
+    );
+    assert_eq!(
+        Some(ParseError::new(2, 1, "a number")),
+        Solver::try_from_str("0 3 6\n\n1 2 3").err()
+    );
 }
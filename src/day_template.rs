@@ -1,34 +1,22 @@
+// https://adventofcode.com/2023/day/{{day}}
+
+use crate::{ParseError, Solution};
+
 pub struct Solver {}
 
-impl Solver {
-    #[must_use]
-    pub fn new(data: Vec<String>) -> Self {
+impl Solution for Solver {
+    type First = u64;
+    type Second = u64;
+
+    fn try_new(data: Vec<String>) -> Result<Self, ParseError> {
         todo!();
     }
 
-    #[must_use]
-    pub fn solve_first(&self) -> u64 {
+    fn solve_first(&self) -> u64 {
         todo!();
     }
 
-    #[must_use]
-    pub fn solve_second(&self) -> u64 {
+    fn solve_second(&self) -> u64 {
         todo!();
     }
 }
-
-#[test]
-pub fn test_solve_first() {
-    let data = vec![todo!()];
-
-    let solver = Solver::new(data);
-    assert_eq!(0, solver.solve_first());
-}
-
-#[test]
-pub fn test_solve_second() {
-    let data = vec![todo!()];
-
-    let solver = Solver::new(data);
-    assert_eq!(0, solver.solve_second());
-}
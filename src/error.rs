@@ -0,0 +1,122 @@
+use std::{
+    fmt::Display,
+    io,
+    path::{Path, PathBuf},
+};
+
+/// Every way loading or solving a day can fail.
+#[derive(Debug)]
+pub enum AocError {
+    MissingInput { path: PathBuf },
+    Io { path: PathBuf, source: io::Error },
+    Encoding { path: PathBuf, line: usize },
+    Parse(ParseError),
+    Config { path: PathBuf, error: ParseError },
+    AlreadyExists { path: PathBuf },
+    Download { url: String, reason: String },
+}
+
+impl AocError {
+    pub(crate) fn open(path: &Path, source: io::Error) -> Self {
+        match source.kind() {
+            io::ErrorKind::NotFound => Self::MissingInput {
+                path: path.to_owned(),
+            },
+            _ => Self::Io {
+                path: path.to_owned(),
+                source,
+            },
+        }
+    }
+
+    pub(crate) fn read(path: &Path, line: usize, source: io::Error) -> Self {
+        match source.kind() {
+            io::ErrorKind::InvalidData => Self::Encoding {
+                path: path.to_owned(),
+                line,
+            },
+            _ => Self::Io {
+                path: path.to_owned(),
+                source,
+            },
+        }
+    }
+}
+
+impl Display for AocError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingInput { path } => write!(f, "input file {} not found", path.display()),
+            Self::Io { path, source } => write!(f, "failed to read {}: {source}", path.display()),
+            Self::Encoding { path, line } => {
+                write!(f, "{}:{line} is not valid UTF-8", path.display())
+            }
+            Self::Parse(e) => write!(f, "failed to parse input: {e}"),
+            Self::Config { path, error } => write!(f, "{}: {error}", path.display()),
+            Self::AlreadyExists { path } => {
+                write!(
+                    f,
+                    "{} already exists, refusing to overwrite",
+                    path.display()
+                )
+            }
+            Self::Download { url, reason } => write!(f, "failed to download {url}: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for AocError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            Self::Parse(e) | Self::Config { error: e, .. } => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseError> for AocError {
+    fn from(value: ParseError) -> Self {
+        Self::Parse(value)
+    }
+}
+
+/// Where and why a day's input could not be parsed. Lines and columns count from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, expected: impl Into<String>) -> Self {
+        Self {
+            line,
+            column,
+            expected: expected.into(),
+        }
+    }
+
+    /// Builds an error pointing at `part`, which must be a slice of `line`, the
+    /// `line_idx`-th (0-based) line of the input.
+    pub fn at(line_idx: usize, line: &str, part: &str, expected: impl Into<String>) -> Self {
+        let offset = (part.as_ptr() as usize)
+            .saturating_sub(line.as_ptr() as usize)
+            .min(line.len());
+        let column = line[..offset].chars().count() + 1;
+        Self::new(line_idx + 1, column, expected)
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}",
+            self.line, self.column, self.expected
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
@@ -0,0 +1,182 @@
+//! Worked examples from the puzzle text, kept as files so a regression case can be
+//! added without touching any code:
+//!
+//! ```text
+//! res/examples/day_8_b.txt   the example input
+//! res/examples/day_8_b.toml  its expected answers, in the answers.toml format
+//! ```
+//!
+//! Each example may list one or both parts; parts without an answer aren't checked.
+
+use std::path::{Path, PathBuf};
+
+use crate::{
+    answers::{Answers, Status},
+    runner, AocError, InputSource, ParseError, Part,
+};
+
+pub const DEFAULT_DIR: &str = "res/examples";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub day: u8,
+    pub path: PathBuf,
+    pub answers: Answers,
+}
+
+impl Example {
+    /// Loads `day_N_<name>.txt` and the expected answers beside it.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, AocError> {
+        let path = path.as_ref();
+        let day = day_of(path).ok_or_else(|| AocError::Config {
+            path: path.to_owned(),
+            error: ParseError::new(1, 1, "a file named day_N_<name>.txt"),
+        })?;
+
+        let answers_path = path.with_extension("toml");
+        let answers = Answers::load(&answers_path)?;
+
+        let misplaced = answers.days().find(|&other| other != day);
+        if misplaced.is_some() || answers.is_empty() {
+            return Err(AocError::Config {
+                path: answers_path,
+                error: ParseError::new(1, 1, format!("answers for day {day} only")),
+            });
+        }
+
+        Ok(Self {
+            day,
+            path: path.to_owned(),
+            answers,
+        })
+    }
+
+    /// Solves every part with an expected answer, describing each mismatch.
+    pub fn check(&self) -> Result<(), Vec<String>> {
+        let parts = Part::BOTH
+            .into_iter()
+            .filter(|&part| self.answers.get(self.day, part).is_some())
+            .collect::<Vec<_>>();
+
+        let failures = runner::run_day(self.day, &InputSource::Path(self.path.clone()), &parts)
+            .into_iter()
+            .filter_map(|outcome| {
+                let expected = self.answers.get(outcome.day, outcome.part)?;
+                let label = format!("{} part {}", self.path.display(), outcome.part.number());
+                match outcome.result {
+                    Ok(actual) => match self.answers.check(outcome.day, outcome.part, actual) {
+                        Status::Pass => None,
+                        _ => Some(format!("{label}: expected {expected}, got {actual}")),
+                    },
+                    Err(e) => Some(format!("{label}: {e}")),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(failures)
+        }
+    }
+}
+
+/// Every example in `dir`, ordered by day and then by name.
+pub fn discover(dir: impl AsRef<Path>) -> Result<Vec<Example>, AocError> {
+    let dir = dir.as_ref();
+    let entries = std::fs::read_dir(dir).map_err(|e| AocError::open(dir, e))?;
+
+    let mut examples = Vec::new();
+    for entry in entries {
+        let path = entry.map_err(|e| AocError::open(dir, e))?.path();
+        if path.extension().is_some_and(|extension| extension == "txt") {
+            examples.push(Example::load(path)?);
+        }
+    }
+
+    examples.sort_by(|a, b| (a.day, &a.path).cmp(&(b.day, &b.path)));
+    Ok(examples)
+}
+
+fn day_of(path: &Path) -> Option<u8> {
+    let stem = path.file_stem()?.to_str()?;
+    let (day, name) = stem.strip_prefix("day_")?.split_once('_')?;
+    if name.is_empty() {
+        return None;
+    }
+    day.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use rstest::rstest;
+
+    use super::{discover, Example, DEFAULT_DIR};
+    use crate::{registry, Part};
+
+    #[rstest]
+    fn test_example(#[files("res/examples/day_*.txt")] path: PathBuf) {
+        let example = Example::load(&path).unwrap();
+        if registry::get(example.day).is_none() {
+            return;
+        }
+
+        if let Err(failures) = example.check() {
+            panic!("{}", failures.join("\n"));
+        }
+    }
+
+    #[test]
+    fn test_every_part_has_an_example() {
+        let examples = discover(DEFAULT_DIR).unwrap();
+
+        for day in registry::all() {
+            for part in Part::BOTH {
+                assert!(
+                    examples.iter().any(|example| example.day == day.number
+                        && example.answers.get(day.number, part).is_some()),
+                    "day {} part {} has no example",
+                    day.number,
+                    part.number()
+                );
+            }
+        }
+    }
+
+    #[cfg(feature = "day-9")]
+    #[test]
+    fn test_load_errors() {
+        let dir = std::env::temp_dir().join(format!("aoc_2023_examples_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        std::fs::write(dir.join("day_9_a.txt"), "0 3 6 9 12 15\n").unwrap();
+        assert!(Example::load(dir.join("day_9_a.txt")).is_err());
+
+        std::fs::write(
+            dir.join("day_9_a.toml"),
+            "[[answer]]\nday = 8\npart = 1\nvalue = 18\n",
+        )
+        .unwrap();
+        assert!(Example::load(dir.join("day_9_a.txt")).is_err());
+
+        std::fs::write(
+            dir.join("day_9_a.toml"),
+            "[[answer]]\nday = 9\npart = 1\nvalue = 17\n",
+        )
+        .unwrap();
+        let example = Example::load(dir.join("day_9_a.txt")).unwrap();
+        assert_eq!(
+            Err(vec![format!(
+                "{} part 1: expected 17, got 18",
+                dir.join("day_9_a.txt").display()
+            )]),
+            example.check()
+        );
+
+        assert!(Example::load(dir.join("example.txt")).is_err());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,333 @@
+//! Downloads puzzle inputs into the input directory, at most once per day.
+//!
+//! Requests go through the [`HttpClient`] trait. [`DefaultClient`] speaks plain HTTP
+//! itself and hands HTTPS URLs to the system `curl`, so no TLS stack is compiled in.
+
+use std::{
+    io::{Read, Write},
+    net::TcpStream,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{input::day_file, AocError};
+
+pub const BASE_URL: &str = "https://adventofcode.com/2023";
+
+/// Holds the session cookie; takes precedence over [`SESSION_FILE`].
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// Default file holding the session cookie, relative to the working directory.
+pub const SESSION_FILE: &str = ".aoc-session";
+
+/// Minimum gap between two requests to the puzzle site.
+pub const REQUEST_INTERVAL: Duration = Duration::from_secs(3);
+
+const USER_AGENT: &str = "github.com/N-Hoque/aoc2023 (aoc_2023 fetch)";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+pub trait HttpClient {
+    fn get(&self, url: &str, headers: &[(&str, &str)]) -> Result<Response, String>;
+}
+
+/// Plain HTTP over a [`TcpStream`], and `curl` for HTTPS.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DefaultClient;
+
+impl HttpClient for DefaultClient {
+    fn get(&self, url: &str, headers: &[(&str, &str)]) -> Result<Response, String> {
+        if url.starts_with("http://") {
+            http_get(url, headers)
+        } else {
+            curl_get(url, headers)
+        }
+    }
+}
+
+fn http_get(url: &str, headers: &[(&str, &str)]) -> Result<Response, String> {
+    let rest = url
+        .strip_prefix("http://")
+        .ok_or_else(|| format!("{url} is not an http URL"))?;
+    let (host, path) = rest.find('/').map_or((rest, "/"), |idx| rest.split_at(idx));
+
+    let mut stream = TcpStream::connect(host).map_err(|e| format!("connecting to {host}: {e}"))?;
+
+    let request = headers.iter().fold(
+        format!("GET {path} HTTP/1.1\r\nHost: {host}\r\nConnection: close\r\n"),
+        |request, (name, value)| request + name + ": " + value + "\r\n",
+    ) + "\r\n";
+    stream
+        .write_all(request.as_bytes())
+        .map_err(|e| format!("sending request to {host}: {e}"))?;
+
+    let mut raw = String::new();
+    stream
+        .read_to_string(&mut raw)
+        .map_err(|e| format!("reading response from {host}: {e}"))?;
+
+    let (head, body) = raw
+        .split_once("\r\n\r\n")
+        .ok_or_else(|| format!("malformed response from {host}"))?;
+    let status = head
+        .split_whitespace()
+        .nth(1)
+        .and_then(|status| status.parse().ok())
+        .ok_or_else(|| format!("malformed status line from {host}"))?;
+
+    Ok(Response {
+        status,
+        body: body.to_string(),
+    })
+}
+
+fn curl_get(url: &str, headers: &[(&str, &str)]) -> Result<Response, String> {
+    // Headers go through stdin so the session cookie never shows up in `ps`
+    let mut child = Command::new("curl")
+        .args(["--silent", "--show-error", "--header", "@-"])
+        .args(["--write-out", "\n%{http_code}", url])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("running curl: {e}"))?;
+
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    for (name, value) in headers {
+        writeln!(stdin, "{name}: {value}").map_err(|e| format!("writing to curl: {e}"))?;
+    }
+    drop(stdin);
+
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("running curl: {e}"))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+
+    let stdout = String::from_utf8(output.stdout).map_err(|_| "response is not UTF-8")?;
+    let (body, status) = stdout
+        .rsplit_once('\n')
+        .ok_or("curl did not report a status")?;
+
+    Ok(Response {
+        status: status
+            .parse()
+            .map_err(|_| format!("{status} is not an HTTP status"))?,
+        body: body.to_string(),
+    })
+}
+
+/// Reads the session cookie from `$AOC_SESSION`, or from `path`.
+pub fn load_session(path: &Path) -> Result<String, AocError> {
+    if let Ok(session) = std::env::var(SESSION_VAR) {
+        return Ok(session.trim().to_string());
+    }
+    std::fs::read_to_string(path)
+        .map(|session| session.trim().to_string())
+        .map_err(|e| AocError::open(path, e))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fetched {
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+pub struct Fetcher<C> {
+    client: C,
+    base_url: String,
+    session: String,
+    cache_dir: PathBuf,
+    interval: Duration,
+    last_request: Option<Instant>,
+}
+
+impl<C: HttpClient> Fetcher<C> {
+    pub fn new(client: C, session: String, cache_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            client,
+            base_url: BASE_URL.to_string(),
+            session,
+            cache_dir: cache_dir.into(),
+            interval: REQUEST_INTERVAL,
+            last_request: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    #[must_use]
+    pub const fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Returns the cached input for `day` if there is one, otherwise downloads it.
+    /// Empty files, such as the placeholder `new-day` creates, don't count as cached.
+    pub fn fetch(&mut self, day: u8) -> Result<Fetched, AocError> {
+        let path = self.cache_dir.join(day_file(day));
+        if std::fs::metadata(&path).is_ok_and(|metadata| metadata.len() > 0) {
+            return Ok(Fetched::Cached(path));
+        }
+
+        self.wait_for_slot();
+
+        let url = format!("{}/day/{day}/input", self.base_url);
+        let cookie = format!("session={}", self.session);
+        let response = self
+            .client
+            .get(&url, &[("Cookie", &cookie), ("User-Agent", USER_AGENT)])
+            .map_err(|reason| AocError::Download {
+                url: url.clone(),
+                reason,
+            })?;
+
+        if response.status != 200 {
+            return Err(AocError::Download {
+                url,
+                reason: format!("server answered {}", response.status),
+            });
+        }
+
+        std::fs::create_dir_all(&self.cache_dir).map_err(|e| AocError::open(&self.cache_dir, e))?;
+        std::fs::write(&path, response.body).map_err(|e| AocError::open(&path, e))?;
+
+        Ok(Fetched::Downloaded(path))
+    }
+
+    fn wait_for_slot(&mut self) {
+        if let Some(last) = self.last_request {
+            if let Some(remaining) = self.interval.checked_sub(last.elapsed()) {
+                thread::sleep(remaining);
+            }
+        }
+        self.last_request = Some(Instant::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+        time::{Duration, Instant},
+    };
+
+    use super::{DefaultClient, Fetched, Fetcher};
+    use crate::AocError;
+
+    /// Serves `responses` in order on a local port, reporting each request line and
+    /// cookie header back through the returned channel.
+    fn stand_in_server(
+        responses: Vec<(u16, &'static str)>,
+    ) -> (String, mpsc::Receiver<(String, String)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}/2023", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut cookie = String::new();
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    if header.trim().is_empty() {
+                        break;
+                    }
+                    if let Some(value) = header.strip_prefix("Cookie: ") {
+                        cookie = value.trim().to_string();
+                    }
+                }
+                sender
+                    .send((request_line.trim().to_string(), cookie))
+                    .unwrap();
+
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} OK\r\nContent-Length: {}\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+        });
+
+        (base_url, receiver)
+    }
+
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc_2023_{name}_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_fetch_caches_and_rate_limits() {
+        let (base_url, requests) = stand_in_server(vec![(200, "1 2 3\n"), (200, "4 5 6\n")]);
+        let dir = temp_dir("fetch");
+
+        let mut fetcher = Fetcher::new(DefaultClient, "abc".to_string(), &dir)
+            .with_base_url(base_url)
+            .with_interval(Duration::from_millis(200));
+
+        let start = Instant::now();
+        assert_eq!(
+            Fetched::Downloaded(dir.join("day_1.txt")),
+            fetcher.fetch(1).unwrap()
+        );
+        assert_eq!(
+            Fetched::Cached(dir.join("day_1.txt")),
+            fetcher.fetch(1).unwrap()
+        );
+        assert_eq!(
+            Fetched::Downloaded(dir.join("day_2.txt")),
+            fetcher.fetch(2).unwrap()
+        );
+        assert!(start.elapsed() >= Duration::from_millis(200));
+
+        assert_eq!(
+            (
+                "GET /2023/day/1/input HTTP/1.1".to_string(),
+                "session=abc".to_string()
+            ),
+            requests.recv().unwrap()
+        );
+        assert_eq!("GET /2023/day/2/input HTTP/1.1", requests.recv().unwrap().0);
+        assert_eq!(
+            "1 2 3\n",
+            std::fs::read_to_string(dir.join("day_1.txt")).unwrap()
+        );
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_fetch_reports_bad_status() {
+        let (base_url, _requests) = stand_in_server(vec![(400, "Please log in")]);
+        let dir = temp_dir("fetch_error");
+
+        let mut fetcher = Fetcher::new(DefaultClient, "expired".to_string(), &dir)
+            .with_base_url(base_url)
+            .with_interval(Duration::ZERO);
+
+        assert!(matches!(fetcher.fetch(3), Err(AocError::Download { .. })));
+        assert!(!dir.join("day_3.txt").exists());
+    }
+}
@@ -0,0 +1,262 @@
+//! Rectangular puzzle maps with one cell per input character.
+//!
+//! Cells are stored row by row, and every iterator here visits them in that order,
+//! top to bottom and left to right.
+
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+/// A cell position. Rows and columns count from 0 at the top left.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Point {
+    #[must_use]
+    pub const fn new(row: usize, col: usize) -> Self {
+        Self { row, col }
+    }
+
+    /// The point `(rows, cols)` away, or `None` past the top or left edge.
+    #[must_use]
+    pub const fn offset(self, (rows, cols): (isize, isize)) -> Option<Self> {
+        match (
+            self.row.checked_add_signed(rows),
+            self.col.checked_add_signed(cols),
+        ) {
+            (Some(row), Some(col)) => Some(Self { row, col }),
+            _ => None,
+        }
+    }
+}
+
+/// Up, right, down and left, as `(rows, cols)` offsets.
+pub const ORTHOGONAL: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+/// The eight surrounding offsets, in reading order.
+pub const ADJACENT: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A grid of `cells` in rows of `width`.
+    #[must_use]
+    pub fn new(width: usize, cells: Vec<T>) -> Self {
+        assert!(
+            cells.len() % width.max(1) == 0,
+            "{} cells don't fill rows of {width}",
+            cells.len()
+        );
+        Self { width, cells }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// A `width` by `height` grid with every cell set to `value`.
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        Self {
+            width,
+            cells: vec![value; width * height],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.cells.len().checked_div(self.width).unwrap_or(0)
+    }
+
+    #[must_use]
+    pub fn contains(&self, point: Point) -> bool {
+        point.col < self.width && point.row * self.width + point.col < self.cells.len()
+    }
+
+    #[must_use]
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.contains(point)
+            .then(|| &self.cells[point.row * self.width + point.col])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        if self.contains(point) {
+            Some(&mut self.cells[point.row * self.width + point.col])
+        } else {
+            None
+        }
+    }
+
+    /// Every point on the grid.
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        (0..self.height()).flat_map(|row| (0..self.width).map(move |col| Point::new(row, col)))
+    }
+
+    /// Every cell with its position.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(&self.cells)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` refuses a zero width, which only an empty grid can have.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    #[must_use]
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(col < self.width, "column {col} is outside the grid");
+        self.cells.iter().skip(col).step_by(self.width)
+    }
+
+    /// The cells in the rectangle between two corners, inclusive, clipped to the grid.
+    pub fn region(
+        &self,
+        top_left: Point,
+        bottom_right: Point,
+    ) -> impl Iterator<Item = (Point, &T)> {
+        let rows = top_left.row..=bottom_right.row.min(self.height().saturating_sub(1));
+        let cols = top_left.col..=bottom_right.col.min(self.width.saturating_sub(1));
+
+        rows.flat_map(move |row| cols.clone().map(move |col| Point::new(row, col)))
+            .filter_map(|point| Some((point, self.get(point)?)))
+    }
+
+    /// The cells up, right, down and left of `point` that are on the grid.
+    pub fn neighbours(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        self.around(point, &ORTHOGONAL)
+    }
+
+    /// The up to eight cells surrounding `point`, in reading order.
+    pub fn adjacent(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        self.around(point, &ADJACENT)
+    }
+
+    fn around<'a>(
+        &'a self,
+        point: Point,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (Point, &'a T)> {
+        offsets.iter().filter_map(move |&offset| {
+            let point = point.offset(offset)?;
+            Some((point, self.get(point)?))
+        })
+    }
+
+    /// The first point holding `value`.
+    pub fn find(&self, value: &T) -> Option<Point>
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .find_map(|(point, cell)| (cell == value).then_some(point))
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{point:?} is outside the grid"))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{point:?} is outside the grid"))
+    }
+}
+
+/// Writes the grid back out one line per row.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (idx, row) in self.rows().enumerate() {
+            if idx > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Grid, Point};
+    use crate::{
+        parsers::{char_grid, parse_all},
+        ParseError,
+    };
+
+    fn grid() -> Grid<char> {
+        parse_all("abc\ndef\nghi", char_grid("a letter")).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = grid();
+
+        assert_eq!((3, 3), (grid.width(), grid.height()));
+        assert_eq!('f', grid[Point::new(1, 2)]);
+        assert_eq!(None, grid.get(Point::new(1, 3)));
+        assert_eq!("abc\ndef\nghi", grid.to_string());
+
+        assert_eq!(
+            Err(ParseError::new(2, 3, "a row as wide as the first")),
+            parse_all("abc\nde", char_grid::<char>("a letter"))
+        );
+        assert_eq!(
+            Err(ParseError::new(1, 2, "a byte")),
+            parse_all("1€", char_grid::<u8>("a byte"))
+        );
+    }
+
+    #[test]
+    fn test_iteration() {
+        let grid = grid();
+        let cells = |cells: &mut dyn Iterator<Item = (Point, &char)>| {
+            cells.map(|(_, c)| *c).collect::<String>()
+        };
+
+        assert_eq!("bfhd", cells(&mut grid.neighbours(Point::new(1, 1))));
+        assert_eq!("fb", cells(&mut grid.neighbours(Point::new(0, 2))));
+        assert_eq!("abcdfghi", cells(&mut grid.adjacent(Point::new(1, 1))));
+        assert_eq!("bde", cells(&mut grid.adjacent(Point::new(0, 0))));
+        assert_eq!(
+            "efhi",
+            cells(&mut grid.region(Point::new(1, 1), Point::new(5, 5)))
+        );
+        assert_eq!("cfi", grid.column(2).collect::<String>());
+        assert_eq!(&['d', 'e', 'f'], grid.row(1));
+        assert_eq!(Some(Point::new(2, 0)), grid.find(&'g'));
+        assert_eq!(None, grid.find(&'z'));
+    }
+}
@@ -0,0 +1,189 @@
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::{Path, PathBuf},
+};
+
+use crate::AocError;
+
+/// Names a directory holding `day_N.txt` files to use instead of `res/`.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+const DEFAULT_DIR: &str = "res";
+
+/// Where a day's puzzle input is read from.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub enum InputSource<'a> {
+    /// `day_N.txt` inside `$AOC_INPUT_DIR`, falling back to `res/`.
+    #[default]
+    Default,
+    /// `day_N.txt` inside the given directory.
+    Dir(PathBuf),
+    /// A single file, used whatever the day.
+    Path(PathBuf),
+    Stdin,
+    Text(&'a str),
+}
+
+impl InputSource<'_> {
+    /// Interprets a command-line argument, where `-` means stdin.
+    #[must_use]
+    pub fn from_arg(arg: &str) -> Self {
+        if arg == "-" {
+            Self::Stdin
+        } else {
+            Self::Path(PathBuf::from(arg))
+        }
+    }
+
+    /// Whether this source can only supply a single day's input.
+    #[must_use]
+    pub const fn is_single(&self) -> bool {
+        matches!(self, Self::Path(_) | Self::Stdin | Self::Text(_))
+    }
+
+    /// The file the day's input is read from, if it comes from a file.
+    #[must_use]
+    pub fn path(&self, day: u8) -> Option<PathBuf> {
+        match self {
+            Self::Default => Some(default_dir().join(day_file(day))),
+            Self::Dir(dir) => Some(dir.join(day_file(day))),
+            Self::Path(path) => Some(path.clone()),
+            Self::Stdin | Self::Text(_) => None,
+        }
+    }
+
+    pub fn lines(&self, day: u8) -> Result<Vec<String>, AocError> {
+        match self {
+            Self::Text(text) => Ok(text.lines().map(str::to_string).collect()),
+            _ => self.reader(day)?.collect(),
+        }
+    }
+
+    /// Opens the input for reading one line at a time.
+    pub fn reader(&self, day: u8) -> Result<LineReader<Box<dyn BufRead + '_>>, AocError> {
+        let open = |path: PathBuf| {
+            let file = File::open(&path).map_err(|e| AocError::open(&path, e))?;
+            Ok(LineReader::new(path, Box::new(BufReader::new(file)) as _))
+        };
+
+        match self {
+            Self::Default => open(default_dir().join(day_file(day))),
+            Self::Dir(dir) => open(dir.join(day_file(day))),
+            Self::Path(path) => open(path.clone()),
+            Self::Stdin => Ok(LineReader::new("<stdin>", Box::new(io::stdin().lock()))),
+            Self::Text(text) => Ok(LineReader::new("<text>", Box::new(text.as_bytes()))),
+        }
+    }
+}
+
+/// Reads lines into a single reused buffer, so memory use follows the longest line
+/// rather than the size of the input. Line endings are stripped, as with
+/// [`BufRead::lines`].
+pub struct LineReader<R> {
+    path: PathBuf,
+    reader: R,
+    line: String,
+    next_idx: usize,
+}
+
+impl<R: BufRead> LineReader<R> {
+    /// `path` is only used to describe where read errors happened.
+    pub fn new(path: impl Into<PathBuf>, reader: R) -> Self {
+        Self {
+            path: path.into(),
+            reader,
+            line: String::new(),
+            next_idx: 0,
+        }
+    }
+
+    /// The next line and its zero-based index, or `None` at the end of the input.
+    pub fn next_line(&mut self) -> Result<Option<(usize, &str)>, AocError> {
+        let line_idx = self.next_idx;
+
+        self.line.clear();
+        let read = self
+            .reader
+            .read_line(&mut self.line)
+            .map_err(|e| AocError::read(&self.path, line_idx + 1, e))?;
+        if read == 0 {
+            return Ok(None);
+        }
+
+        self.next_idx += 1;
+        let line = self.line.strip_suffix('\n').unwrap_or(&self.line);
+        Ok(Some((line_idx, line.strip_suffix('\r').unwrap_or(line))))
+    }
+
+    /// Reads every remaining line.
+    pub fn collect(mut self) -> Result<Vec<String>, AocError> {
+        let mut lines = Vec::new();
+        while let Some((_, line)) = self.next_line()? {
+            lines.push(line.to_string());
+        }
+        Ok(lines)
+    }
+}
+
+/// `$AOC_INPUT_DIR` if set, otherwise `res/`.
+#[must_use]
+pub fn default_dir() -> PathBuf {
+    std::env::var_os(INPUT_DIR_VAR).map_or_else(|| PathBuf::from(DEFAULT_DIR), PathBuf::from)
+}
+
+pub(crate) fn day_file(day: u8) -> String {
+    format!("day_{day}.txt")
+}
+
+pub fn read_lines(path: impl AsRef<Path>) -> Result<Vec<String>, AocError> {
+    InputSource::Path(path.as_ref().to_owned()).lines(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_lines, InputSource, LineReader};
+    use crate::AocError;
+
+    #[test]
+    fn test_read_lines_errors() {
+        let dir = std::env::temp_dir().join(format!("aoc_2023_read_lines_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let missing = read_lines(dir.join("missing.txt"));
+        assert!(matches!(missing, Err(AocError::MissingInput { .. })));
+
+        let invalid = dir.join("invalid.txt");
+        std::fs::write(&invalid, b"fine\nbad \xff byte\n").unwrap();
+        assert!(matches!(
+            read_lines(&invalid),
+            Err(AocError::Encoding { line: 2, .. })
+        ));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_sources() {
+        let dir = std::env::temp_dir().join(format!("aoc_2023_sources_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("day_3.txt"), "a\nb\n").unwrap();
+
+        let expected = vec!["a".to_string(), "b".to_string()];
+        assert_eq!(expected, InputSource::Text("a\nb").lines(3).unwrap());
+        assert_eq!(expected, InputSource::Dir(dir.clone()).lines(3).unwrap());
+        assert_eq!(
+            expected,
+            InputSource::Path(dir.join("day_3.txt")).lines(9).unwrap()
+        );
+        assert!(InputSource::Dir(dir.clone()).lines(4).is_err());
+
+        let mut reader = LineReader::new("<test>", &b"a\r\n\nb"[..]);
+        assert_eq!(Some((0, "a")), reader.next_line().unwrap());
+        assert_eq!(Some((1, "")), reader.next_line().unwrap());
+        assert_eq!(Some((2, "b")), reader.next_line().unwrap());
+        assert_eq!(None, reader.next_line().unwrap());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,334 @@
+//! Half-open integer intervals, and sets of them, for puzzles that push whole ranges
+//! of numbers through a transformation instead of one number at a time.
+
+use std::cmp::Ordering;
+
+/// The numbers `start..end`, empty unless `start < end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl Interval {
+    #[must_use]
+    pub const fn new(start: u64, end: u64) -> Self {
+        Self { start, end }
+    }
+
+    /// The `len` numbers from `start`, cut short at `u64::MAX`.
+    #[must_use]
+    pub const fn with_len(start: u64, len: u64) -> Self {
+        Self::new(start, start.saturating_add(len))
+    }
+
+    #[must_use]
+    pub const fn is_empty(self) -> bool {
+        self.start >= self.end
+    }
+
+    #[must_use]
+    pub const fn len(self) -> u64 {
+        self.end.saturating_sub(self.start)
+    }
+
+    #[must_use]
+    pub const fn contains(self, value: u64) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    /// The numbers in both intervals, if there are any.
+    #[must_use]
+    pub fn intersection(self, other: Self) -> Option<Self> {
+        let overlap = Self::new(self.start.max(other.start), self.end.min(other.end));
+        (!overlap.is_empty()).then_some(overlap)
+    }
+}
+
+/// Disjoint intervals kept sorted, with touching ones merged, so equal sets compare
+/// equal however they were built.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntervalSet(Vec<Interval>);
+
+impl IntervalSet {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    pub fn insert(&mut self, interval: Interval) {
+        if interval.is_empty() {
+            return;
+        }
+
+        // Everything from `first` to `last` touches the new interval and merges into it.
+        let first = self.0.partition_point(|other| other.end < interval.start);
+        let last = self.0.partition_point(|other| other.start <= interval.end);
+        let merged = self.0[first..last].iter().fold(interval, |merged, other| {
+            Interval::new(merged.start.min(other.start), merged.end.max(other.end))
+        });
+        self.0.splice(first..last, [merged]);
+    }
+
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        for &interval in &other.0 {
+            union.insert(interval);
+        }
+        union
+    }
+
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intersection = Vec::new();
+        let (mut left, mut right) = (self.0.iter().peekable(), other.0.iter().peekable());
+
+        while let (Some(&&a), Some(&&b)) = (left.peek(), right.peek()) {
+            if let Some(overlap) = a.intersection(b) {
+                intersection.push(overlap);
+            }
+            match a.end.cmp(&b.end) {
+                Ordering::Less => left.next(),
+                Ordering::Greater => right.next(),
+                Ordering::Equal => {
+                    left.next();
+                    right.next()
+                }
+            };
+        }
+
+        Self(intersection)
+    }
+
+    /// The numbers in `self` but not in `other`.
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut difference = Vec::new();
+        let mut removed = other.0.iter().peekable();
+
+        for &interval in &self.0 {
+            let mut start = interval.start;
+            while let Some(&&cut) = removed.peek() {
+                if cut.start >= interval.end {
+                    break;
+                }
+                if cut.start > start {
+                    difference.push(Interval::new(start, cut.start));
+                }
+                start = start.max(cut.end);
+                if cut.end > interval.end {
+                    break;
+                }
+                removed.next();
+            }
+            if start < interval.end {
+                difference.push(Interval::new(start, interval.end));
+            }
+        }
+
+        Self(difference)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Interval> + '_ {
+        self.0.iter().copied()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// How many numbers the set holds.
+    #[must_use]
+    pub fn count(&self) -> u64 {
+        self.0.iter().map(|interval| interval.len()).sum()
+    }
+
+    #[must_use]
+    pub fn min(&self) -> Option<u64> {
+        self.0.first().map(|interval| interval.start)
+    }
+}
+
+impl From<Interval> for IntervalSet {
+    fn from(interval: Interval) -> Self {
+        let mut set = Self::new();
+        set.insert(interval);
+        set
+    }
+}
+
+impl FromIterator<Interval> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Interval>>(intervals: I) -> Self {
+        let mut set = Self::new();
+        for interval in intervals {
+            set.insert(interval);
+        }
+        set
+    }
+}
+
+/// Moves the numbers in `source` so that `source.start` lands on `destination`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Offset {
+    source: Interval,
+    destination: u64,
+}
+
+impl Offset {
+    /// The offset, or `None` if it would move numbers past `u64::MAX`.
+    #[must_use]
+    pub const fn new(source: Interval, destination: u64) -> Option<Self> {
+        match destination.checked_add(source.len()) {
+            Some(_) => Some(Self {
+                source,
+                destination,
+            }),
+            None => None,
+        }
+    }
+
+    #[must_use]
+    pub const fn get(self, value: u64) -> Option<u64> {
+        if self.source.contains(value) {
+            Some(self.destination + (value - self.source.start))
+        } else {
+            None
+        }
+    }
+
+    /// Moves part of `source`, which [`Offset::new`] made sure stays below `u64::MAX`.
+    const fn shift(self, interval: Interval) -> Interval {
+        Interval::new(
+            self.destination + (interval.start - self.source.start),
+            self.destination + (interval.end - self.source.start),
+        )
+    }
+}
+
+/// A piecewise map made of [`Offset`]s. Numbers outside every piece map to
+/// themselves, and where pieces overlap the first one wins.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OffsetMap(Vec<Offset>);
+
+impl OffsetMap {
+    #[must_use]
+    pub fn pieces(&self) -> &[Offset] {
+        &self.0
+    }
+
+    #[must_use]
+    pub fn get(&self, value: u64) -> u64 {
+        self.0
+            .iter()
+            .find_map(|offset| offset.get(value))
+            .unwrap_or(value)
+    }
+
+    /// Splits `set` wherever a piece starts or ends and maps every part.
+    #[must_use]
+    pub fn apply(&self, set: &IntervalSet) -> IntervalSet {
+        let mut unmapped = set.clone();
+        let mut mapped = IntervalSet::new();
+
+        for &offset in &self.0 {
+            let hit = unmapped.intersection(&offset.source.into());
+            for interval in hit.iter() {
+                mapped.insert(offset.shift(interval));
+            }
+            unmapped = unmapped.difference(&hit);
+        }
+
+        mapped.union(&unmapped)
+    }
+}
+
+impl FromIterator<Offset> for OffsetMap {
+    fn from_iter<I: IntoIterator<Item = Offset>>(offsets: I) -> Self {
+        Self(offsets.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Interval, IntervalSet, Offset, OffsetMap};
+
+    fn set(intervals: &[(u64, u64)]) -> IntervalSet {
+        intervals
+            .iter()
+            .map(|&(start, end)| Interval::new(start, end))
+            .collect()
+    }
+
+    /// The numbers below 40 in `set`, one by one.
+    fn members(set: &IntervalSet) -> Vec<u64> {
+        (0..40)
+            .filter(|&n| set.iter().any(|interval| interval.contains(n)))
+            .collect()
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = set(&[(0, 5), (10, 15), (4, 8), (20, 20)]);
+        let b = set(&[(3, 12), (14, 30)]);
+
+        assert_eq!(set(&[(0, 8), (10, 15)]), a);
+        assert_eq!(set(&[(0, 8), (10, 15), (8, 10)]), set(&[(0, 15)]));
+        assert_eq!(13, a.count());
+
+        for (result, expected) in [
+            (a.union(&b), (|x, y| x || y) as fn(bool, bool) -> bool),
+            (a.intersection(&b), |x: bool, y: bool| x && y),
+            (a.difference(&b), |x: bool, y: bool| x && !y),
+            (b.difference(&a), |x: bool, y: bool| y && !x),
+        ] {
+            let expected = (0..40)
+                .filter(|n| expected(members(&a).contains(n), members(&b).contains(n)))
+                .collect::<Vec<_>>();
+            assert_eq!(expected, members(&result));
+        }
+
+        assert_eq!(set(&[(3, 8), (10, 12), (14, 15)]), a.intersection(&b));
+        assert_eq!(set(&[(0, 3), (12, 14)]), a.difference(&b));
+        assert!(a.difference(&a).is_empty());
+    }
+
+    #[test]
+    fn test_offset_map() {
+        let map = [(10, 20, 100), (15, 25, 200), (30, 31, 0)]
+            .into_iter()
+            .map(|(start, end, destination)| {
+                Offset::new(Interval::new(start, end), destination).unwrap()
+            })
+            .collect::<OffsetMap>();
+
+        let seeds = set(&[(5, 27), (30, 32)]);
+        let mapped = map.apply(&seeds);
+
+        let expected = members(&seeds)
+            .into_iter()
+            .map(|n| map.get(n))
+            .map(|n| Interval::new(n, n + 1))
+            .collect::<IntervalSet>();
+        assert_eq!(expected, mapped);
+        assert_eq!(
+            set(&[(0, 1), (5, 10), (25, 27), (31, 32), (100, 110), (205, 210)]),
+            mapped
+        );
+        assert_eq!(Some(0), mapped.min());
+    }
+
+    #[test]
+    fn test_offsets_stay_in_range() {
+        let top = Offset::new(Interval::new(10, 20), u64::MAX - 10).unwrap();
+        assert_eq!(Some(u64::MAX - 1), top.get(19));
+        assert_eq!(
+            set(&[(20, 25), (u64::MAX - 5, u64::MAX)]),
+            OffsetMap::from_iter([top]).apply(&set(&[(15, 25)]))
+        );
+
+        assert_eq!(None, Offset::new(Interval::new(10, 20), u64::MAX - 9));
+        assert_eq!(None, Offset::new(Interval::with_len(0, u64::MAX), 1));
+    }
+}
@@ -0,0 +1,350 @@
+//! Answers tried on the puzzle site and what it said about each, in the same TOML
+//! subset as the answers:
+//!
+//! ```toml
+//! [[attempt]]
+//! day = 1
+//! part = 2
+//! value = 54530
+//! verdict = "too high"   # or "correct", "wrong", "too low"
+//! ```
+//!
+//! A correct attempt counts as a known answer, and the "too high" and "too low" hints
+//! rule out every value beyond them.
+
+use std::{collections::BTreeMap, fmt::Display, io, path::Path};
+
+use crate::{answers::Answers, toml, Answer, AocError, ParseError, Part};
+
+pub const DEFAULT_PATH: &str = "res/ledger.toml";
+
+/// What the puzzle site said about an answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+}
+
+impl std::str::FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Self::Correct),
+            "wrong" => Ok(Self::Wrong),
+            "too high" => Ok(Self::TooHigh),
+            "too low" => Ok(Self::TooLow),
+            x => Err(format!("{x} is not a valid verdict")),
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Correct => write!(f, "correct"),
+            Self::Wrong => write!(f, "wrong"),
+            Self::TooHigh => write!(f, "too high"),
+            Self::TooLow => write!(f, "too low"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Attempt {
+    pub value: Answer,
+    pub verdict: Verdict,
+}
+
+/// What the ledger knows about an answer that hasn't been submitted yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Judgement {
+    /// Nothing: it hasn't been tried and is within the bounds.
+    Untried,
+    /// It was tried and is correct.
+    Correct,
+    /// It was tried and the site gave this verdict.
+    Tried(Verdict),
+    /// It wasn't tried, but a different answer is known to be correct.
+    NotCorrect(Answer),
+    /// It wasn't tried, but is higher than an answer that was too high.
+    AboveBound(Answer),
+    /// It wasn't tried, but is lower than an answer that was too low.
+    BelowBound(Answer),
+}
+
+impl Judgement {
+    /// Whether submitting the answer is certain to be wasted: it is outside the bounds,
+    /// or another answer is already correct.
+    #[must_use]
+    pub const fn is_rejected(self) -> bool {
+        matches!(
+            self,
+            Self::NotCorrect(_) | Self::AboveBound(_) | Self::BelowBound(_)
+        )
+    }
+}
+
+impl Display for Judgement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Untried => write!(f, "not tried yet"),
+            Self::Correct => write!(f, "correct"),
+            Self::Tried(verdict) => write!(f, "known to be {verdict}"),
+            Self::NotCorrect(correct) => write!(f, "wrong, {correct} is correct"),
+            Self::AboveBound(bound) => write!(f, "too high, as {bound} already was"),
+            Self::BelowBound(bound) => write!(f, "too low, as {bound} already was"),
+        }
+    }
+}
+
+/// Every attempt at each day and part, in the order they were made.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Ledger(BTreeMap<(u8, Part), Vec<Attempt>>);
+
+impl Ledger {
+    /// Reads the ledger at `path`, which starts out empty if there's no file yet.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, AocError> {
+        let path = path.as_ref();
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(AocError::open(path, e)),
+        };
+        Self::parse(&text).map_err(|error| AocError::Config {
+            path: path.to_owned(),
+            error,
+        })
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), AocError> {
+        let path = path.as_ref();
+        std::fs::write(path, self.to_string()).map_err(|source| AocError::Io {
+            path: path.to_owned(),
+            source,
+        })
+    }
+
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        let mut ledger = Self::default();
+
+        for table in toml::tables(text, "attempt")? {
+            let (mut day, mut part, mut value, mut verdict) = (None, None, None, None);
+            for field in &table.fields {
+                match field.key {
+                    "day" => day = Some(field.number()?),
+                    "part" => part = Some(field.part()?),
+                    "value" => value = Some(field.number()?),
+                    "verdict" => {
+                        verdict = Some(
+                            field
+                                .string()
+                                .and_then(|verdict| verdict.parse().ok())
+                                .ok_or_else(|| {
+                                    field.invalid(
+                                        "\"correct\", \"wrong\", \"too high\" or \"too low\"",
+                                    )
+                                })?,
+                        );
+                    }
+                    _ => return Err(field.unknown("day, part, value or verdict")),
+                }
+            }
+
+            let day = day.ok_or_else(|| table.missing("day"))?;
+            let part = part.ok_or_else(|| table.missing("part"))?;
+            let value = value.ok_or_else(|| table.missing("value"))?;
+            let verdict = verdict.ok_or_else(|| table.missing("verdict"))?;
+            ledger.record(day, part, value, verdict);
+        }
+
+        Ok(ledger)
+    }
+
+    #[must_use]
+    pub fn attempts(&self, day: u8, part: Part) -> &[Attempt] {
+        self.0.get(&(day, part)).map_or(&[], Vec::as_slice)
+    }
+
+    /// Adds an attempt, or changes the verdict of an earlier one with the same value.
+    pub fn record(&mut self, day: u8, part: Part, value: Answer, verdict: Verdict) {
+        let attempts = self.0.entry((day, part)).or_default();
+        match attempts
+            .iter_mut()
+            .find(|attempt| attempt.value.as_i128() == value.as_i128())
+        {
+            Some(attempt) => attempt.verdict = verdict,
+            None => attempts.push(Attempt { value, verdict }),
+        }
+    }
+
+    #[must_use]
+    pub fn correct(&self, day: u8, part: Part) -> Option<Answer> {
+        self.attempts(day, part)
+            .iter()
+            .find(|attempt| attempt.verdict == Verdict::Correct)
+            .map(|attempt| attempt.value)
+    }
+
+    /// The correct attempts, to check answers against.
+    #[must_use]
+    pub fn answers(&self) -> Answers {
+        let mut answers = Answers::default();
+        for &(day, part) in self.0.keys() {
+            if let Some(correct) = self.correct(day, part) {
+                answers.insert(day, part, correct);
+            }
+        }
+        answers
+    }
+
+    #[must_use]
+    pub fn judge(&self, day: u8, part: Part, candidate: Answer) -> Judgement {
+        let attempts = self.attempts(day, part);
+        let value = candidate.as_i128();
+
+        if let Some(attempt) = attempts
+            .iter()
+            .find(|attempt| attempt.value.as_i128() == value)
+        {
+            return match attempt.verdict {
+                Verdict::Correct => Judgement::Correct,
+                verdict => Judgement::Tried(verdict),
+            };
+        }
+        if let Some(correct) = self.correct(day, part) {
+            return Judgement::NotCorrect(correct);
+        }
+
+        let bound = |verdict| {
+            attempts
+                .iter()
+                .filter(move |attempt| attempt.verdict == verdict)
+                .map(|attempt| attempt.value)
+        };
+        if let Some(high) = bound(Verdict::TooHigh).min_by_key(|high| high.as_i128()) {
+            if value > high.as_i128() {
+                return Judgement::AboveBound(high);
+            }
+        }
+        if let Some(low) = bound(Verdict::TooLow).max_by_key(|low| low.as_i128()) {
+            if value < low.as_i128() {
+                return Judgement::BelowBound(low);
+            }
+        }
+
+        Judgement::Untried
+    }
+}
+
+/// Serialises back into the format [`Ledger::parse`] reads.
+impl Display for Ledger {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let attempts = self
+            .0
+            .iter()
+            .flat_map(|(key, attempts)| attempts.iter().map(move |attempt| (key, attempt)));
+        for (idx, ((day, part), attempt)) in attempts.enumerate() {
+            if idx > 0 {
+                writeln!(f)?;
+            }
+            writeln!(f, "[[attempt]]")?;
+            writeln!(f, "day = {day}")?;
+            writeln!(f, "part = {}", part.number())?;
+            writeln!(f, "value = {}", attempt.value)?;
+            writeln!(f, "verdict = \"{}\"", attempt.verdict)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Judgement, Ledger, Verdict};
+    use crate::{Answer, ParseError, Part};
+
+    const LEDGER: &str = "\
+[[attempt]]
+day = 1
+part = 2
+value = 600
+verdict = \"too high\"
+
+[[attempt]]
+day = 1
+part = 2
+value = 400 # a guess
+verdict = \"too low\"
+
+[[attempt]]
+day = 1
+part = 2
+value = 500
+verdict = \"wrong\"
+
+[[attempt]]
+day = 9
+part = 1
+value = -3
+verdict = \"correct\"
+";
+
+    #[test]
+    fn test_parse_round_trip() {
+        let ledger = Ledger::parse(LEDGER).unwrap();
+
+        assert_eq!(3, ledger.attempts(1, Part::Second).len());
+        assert_eq!(Some(Answer::Signed(-3)), ledger.correct(9, Part::First));
+        assert_eq!(None, ledger.correct(1, Part::Second));
+        assert_eq!(
+            Some(Answer::Signed(-3)),
+            ledger.answers().get(9, Part::First)
+        );
+
+        assert_eq!(ledger, Ledger::parse(&ledger.to_string()).unwrap());
+    }
+
+    #[test]
+    fn test_judge() {
+        let mut ledger = Ledger::parse(LEDGER).unwrap();
+        let judge = |ledger: &Ledger, value: u64| ledger.judge(1, Part::Second, value.into());
+
+        assert_eq!(Judgement::Untried, judge(&ledger, 450));
+        assert_eq!(Judgement::Tried(Verdict::Wrong), judge(&ledger, 500));
+        assert_eq!(Judgement::Tried(Verdict::TooHigh), judge(&ledger, 600));
+        assert_eq!(Judgement::AboveBound(600_u64.into()), judge(&ledger, 601));
+        assert_eq!(Judgement::BelowBound(400_u64.into()), judge(&ledger, 12));
+        assert!(judge(&ledger, 12).is_rejected());
+        assert!(!judge(&ledger, 500).is_rejected());
+
+        ledger.record(1, Part::Second, 450_u64.into(), Verdict::Correct);
+        assert_eq!(Judgement::Correct, judge(&ledger, 450));
+        assert_eq!(Judgement::NotCorrect(450_u64.into()), judge(&ledger, 451));
+
+        ledger.record(1, Part::Second, 450_u64.into(), Verdict::Wrong);
+        assert_eq!(4, ledger.attempts(1, Part::Second).len());
+        assert_eq!(Judgement::Untried, judge(&ledger, 451));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Err(ParseError::new(1, 1, "\"[[attempt]]\"")),
+            Ledger::parse("day = 1")
+        );
+        assert_eq!(
+            Err(ParseError::new(
+                2,
+                11,
+                "\"correct\", \"wrong\", \"too high\" or \"too low\""
+            )),
+            Ledger::parse("[[attempt]]\nverdict = \"close\"")
+        );
+        assert_eq!(
+            Err(ParseError::new(1, 1, "a verdict key")),
+            Ledger::parse("[[attempt]]\nday = 1\npart = 1\nvalue = 3")
+        );
+    }
+}
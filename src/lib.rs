@@ -1,21 +1,54 @@
 #![warn(clippy::pedantic, clippy::nursery)]
 #![allow(clippy::missing_panics_doc, clippy::missing_errors_doc)]
 
-use std::io::BufRead;
+pub mod answers;
+pub mod bench;
+pub mod cli;
+pub mod error;
+pub mod examples;
+pub mod fetch;
+pub mod grid;
+pub mod input;
+pub mod interval;
+pub mod json;
+pub mod ledger;
+pub mod math;
+pub mod parsers;
+pub mod registry;
+pub mod render;
+pub mod repl;
+pub mod runner;
+pub mod scaffold;
+pub mod solution;
+pub mod toml;
+pub mod trace;
+pub mod watch;
 
+#[cfg(feature = "day-1")]
 pub mod day_1;
+#[cfg(feature = "day-10")]
 pub mod day_10;
+#[cfg(feature = "day-2")]
 pub mod day_2;
+#[cfg(feature = "day-3")]
 pub mod day_3;
+#[cfg(feature = "day-4")]
 pub mod day_4;
+#[cfg(feature = "day-5")]
 pub mod day_5;
+#[cfg(feature = "day-6")]
 pub mod day_6;
+#[cfg(feature = "day-7")]
 pub mod day_7;
+#[cfg(feature = "day-8")]
 pub mod day_8;
+#[cfg(feature = "day-9")]
 pub mod day_9;
 
-pub fn read_file(day: u8) -> Vec<String> {
-    let data = std::fs::File::open(format!("res/day_{day}.txt")).unwrap();
-    let reader = std::io::BufReader::new(data);
-    reader.lines().map_while(Result::ok).collect::<Vec<_>>()
+pub use error::{AocError, ParseError};
+pub use input::{read_lines, InputSource};
+pub use solution::{Answer, Part, Solution};
+
+pub fn read_file(day: u8) -> Result<Vec<String>, AocError> {
+    InputSource::Default.lines(day)
 }
@@ -1,23 +1,13 @@
-use aoc_2023::read_file;
+use std::process::ExitCode;
 
-macro_rules! solve_day {
-    ($day:expr,$day_mod:ident) => {
-        let data = read_file($day);
-        let solver = aoc_2023::$day_mod::Solver::new(data);
-        println!("Day {} [1/2]: {}", $day, solver.solve_first());
-        println!("Day {} [2/2]: {}", $day, solver.solve_second());
-    };
-}
+use aoc_2023::cli::{self, Command};
 
-fn main() {
-    solve_day!(1, day_1);
-    solve_day!(2, day_2);
-    solve_day!(3, day_3);
-    solve_day!(4, day_4);
-    solve_day!(5, day_5);
-    solve_day!(6, day_6);
-    solve_day!(7, day_7);
-    solve_day!(8, day_8);
-    solve_day!(9, day_9);
-    solve_day!(10, day_10);
+fn main() -> ExitCode {
+    match Command::parse(std::env::args().skip(1)) {
+        Ok(command) => cli::run(command),
+        Err(e) => {
+            eprintln!("{e}\n\n{}", cli::USAGE);
+            ExitCode::FAILURE
+        }
+    }
 }
@@ -0,0 +1,137 @@
+//! Number theory shared by the days that need to line up cycles.
+
+#[must_use]
+pub const fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// The least common multiple of every number, or `None` if it doesn't fit in a
+/// `u64`. An empty list gives 1, and any zero makes the result 0.
+pub fn lcm(numbers: impl IntoIterator<Item = u64>) -> Option<u64> {
+    numbers.into_iter().try_fold(1_u64, |acc, n| {
+        if acc == 0 || n == 0 {
+            return Some(0);
+        }
+        (acc / gcd(acc, n)).checked_mul(n)
+    })
+}
+
+/// The prime factors of `n` with their multiplicities, smallest first. 0 and 1 have
+/// none.
+#[must_use]
+pub fn factorize(mut n: u64) -> Vec<(u64, u32)> {
+    let mut factors = Vec::new();
+
+    let mut factor = 2;
+    while factor <= n / factor {
+        let mut power = 0;
+        while n % factor == 0 {
+            n /= factor;
+            power += 1;
+        }
+        if power > 0 {
+            factors.push((factor, power));
+        }
+        factor += if factor == 2 { 1 } else { 2 };
+    }
+    if n > 1 {
+        factors.push((n, 1));
+    }
+
+    factors
+}
+
+/// Solves `x ≡ residue (mod modulus)` for every pair at once.
+///
+/// Gives the smallest such `x` and the modulus it repeats with. The moduli don't need
+/// to be coprime. Returns `None` if the congruences contradict each other, a modulus is 0, or the
+/// combined modulus doesn't fit in a `u64`.
+pub fn crt(congruences: impl IntoIterator<Item = (u64, u64)>) -> Option<(u64, u64)> {
+    let (mut x, mut m) = (0_i128, 1_i128);
+
+    for (residue, modulus) in congruences {
+        if modulus == 0 {
+            return None;
+        }
+        let (residue, modulus) = (i128::from(residue % modulus), i128::from(modulus));
+
+        // Find k with x + m * k ≡ residue (mod modulus).
+        let (g, inverse, _) = extended_gcd(m, modulus);
+        let difference = residue - x;
+        if difference % g != 0 {
+            return None;
+        }
+        let step = modulus / g;
+        let k = (difference / g)
+            .rem_euclid(step)
+            .checked_mul(inverse.rem_euclid(step))?
+            % step;
+
+        x = m.checked_mul(k).and_then(|mk| x.checked_add(mk))?;
+        m = m.checked_mul(step).filter(|&m| m <= i128::from(u64::MAX))?;
+    }
+
+    Some((u64::try_from(x).ok()?, u64::try_from(m).ok()?))
+}
+
+/// `(g, s, t)` with `a * s + b * t = g = gcd(a, b)`.
+const fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_rem, mut rem) = (a, b);
+    let (mut old_s, mut new_s) = (1, 0);
+    let (mut old_t, mut new_t) = (0, 1);
+
+    while rem != 0 {
+        let quotient = old_rem / rem;
+        (old_rem, rem) = (rem, old_rem - quotient * rem);
+        (old_s, new_s) = (new_s, old_s - quotient * new_s);
+        (old_t, new_t) = (new_t, old_t - quotient * new_t);
+    }
+
+    (old_rem, old_s, old_t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{crt, factorize, gcd, lcm};
+
+    #[test]
+    fn test_gcd_and_lcm() {
+        assert_eq!(6, gcd(12, 18));
+        assert_eq!(7, gcd(0, 7));
+
+        assert_eq!(Some(12), lcm([4, 6]));
+        assert_eq!(Some(72), lcm([8, 12, 18]));
+        assert_eq!(Some(1), lcm([]));
+        assert_eq!(Some(0), lcm([3, 0, 5]));
+        assert_eq!(None, lcm([u64::MAX, u64::MAX - 1]));
+    }
+
+    #[test]
+    fn test_factorize() {
+        assert_eq!(vec![(2, 3), (3, 2), (5, 1)], factorize(360));
+        assert_eq!(vec![(2, 2)], factorize(4));
+        assert_eq!(vec![(3, 4)], factorize(81));
+        assert_eq!(vec![(1_000_000_007, 1)], factorize(1_000_000_007));
+        assert_eq!(vec![(999_983, 2)], factorize(999_983 * 999_983));
+        assert!(factorize(1).is_empty());
+        assert!(factorize(0).is_empty());
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(Some((23, 105)), crt([(2, 3), (3, 5), (2, 7)]));
+        assert_eq!(Some((10, 12)), crt([(2, 4), (4, 6)]));
+        assert_eq!(Some((0, 1)), crt([]));
+        assert_eq!(None, crt([(1, 2), (0, 4)]));
+        assert_eq!(None, crt([(1, 0)]));
+        assert_eq!(None, crt([(0, u64::MAX), (0, u64::MAX - 1)]));
+        assert_eq!(None, crt([(2, u64::MAX), (1, u64::MAX - 1)]));
+        assert_eq!(
+            Some((309_308_344_532_033_940, 18_446_743_979_220_271_189)),
+            crt([(123_456_789, 4_294_967_291), (987_654_321, 4_294_967_279)])
+        );
+    }
+}
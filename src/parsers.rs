@@ -0,0 +1,303 @@
+//! Parsing shared by the days' `try_new` constructors.
+//!
+//! The plain string helpers take the 0-based index and full text of the line being
+//! parsed so that failures can point at the exact column. The winnow combinators
+//! further down cover the shapes puzzle inputs keep coming back to, and
+//! [`parse_line`] and [`parse_all`] turn their failures into the same [`ParseError`].
+
+use std::str::FromStr;
+
+use winnow::{
+    ascii::{alphanumeric1, line_ending, multispace0, space0, space1},
+    combinator::{
+        cut_err, delimited, eof, not, peek, preceded, separated, separated_pair, terminated,
+    },
+    error::{AddContext, ContextError, ErrMode, StrContext, StrContextValue},
+    stream::{Accumulate, Stream},
+    token::{one_of, take, take_while},
+    PResult, Parser,
+};
+
+use crate::{error::ParseError, grid::Grid};
+
+pub fn number<T: FromStr>(line_idx: usize, line: &str, token: &str) -> Result<T, ParseError> {
+    token
+        .parse()
+        .map_err(|_| ParseError::at(line_idx, line, token, "a number"))
+}
+
+/// Parses a whitespace-separated list of numbers.
+pub fn numbers<T: FromStr>(line_idx: usize, line: &str, list: &str) -> Result<Vec<T>, ParseError> {
+    list.split_whitespace()
+        .map(|token| number(line_idx, line, token))
+        .collect()
+}
+
+/// Describes what a parser expected, for the message of the [`ParseError`] it fails
+/// with.
+#[must_use]
+pub const fn expected(description: &'static str) -> StrContext {
+    StrContext::Expected(StrContextValue::Description(description))
+}
+
+/// Matches `literal` exactly, naming it in errors.
+#[must_use]
+pub fn tag<'s>(literal: &'static str) -> impl Parser<&'s str, &'s str, ContextError> {
+    winnow::token::literal(literal).context(StrContext::Expected(StrContextValue::StringLiteral(
+        literal,
+    )))
+}
+
+/// A number that must fit in `T`. A word that isn't one fails without backtracking,
+/// so only use this where nothing else could follow.
+pub fn decimal<T: FromStr>(input: &mut &str) -> PResult<T> {
+    let start = input.checkpoint();
+    let token = take_while(1.., |c: char| c.is_ascii_alphanumeric())
+        .context(expected("a number"))
+        .parse_next(input)?;
+
+    token.parse().map_err(|_| {
+        input.reset(start);
+        ErrMode::Cut(ContextError::new().add_context(input, expected("a number")))
+    })
+}
+
+/// Space-separated numbers after any leading spaces, gathered into a `Vec`, or into
+/// `()` to only check them. The list ends at the first word that doesn't start with
+/// a digit.
+pub fn number_list<T: FromStr, C: Accumulate<T>>(input: &mut &str) -> PResult<C> {
+    preceded(
+        space0,
+        separated(
+            1..,
+            decimal::<T>,
+            (space1, peek(one_of(|c: char| c.is_ascii_digit()))),
+        ),
+    )
+    .parse_next(input)
+}
+
+/// `label: value`, with any spaces after the colon.
+pub fn labelled<'s, L, O>(
+    label: impl Parser<&'s str, L, ContextError>,
+    value: impl Parser<&'s str, O, ContextError>,
+) -> impl Parser<&'s str, (L, O), ContextError> {
+    separated_pair(label, (tag(":"), space0), value)
+}
+
+/// One `line` per line, up to an empty line or the end of the input. A line that
+/// doesn't parse is an error rather than the end of the list.
+pub fn lines<'s, O, C: Accumulate<O>>(
+    line: impl Parser<&'s str, O, ContextError>,
+) -> impl Parser<&'s str, C, ContextError> {
+    separated(1.., cut_err(line), (line_ending, not(line_ending), more))
+}
+
+/// The empty line that ends a section.
+pub fn blank_line<'s>(input: &mut &'s str) -> PResult<&'s str> {
+    (line_ending, line_ending.context(expected("an empty line")))
+        .recognize()
+        .parse_next(input)
+}
+
+/// Sections separated by empty lines, each parsed by `section`.
+pub fn sections<'s, O, C: Accumulate<O>>(
+    section: impl Parser<&'s str, O, ContextError>,
+) -> impl Parser<&'s str, C, ContextError> {
+    separated(1.., cut_err(section), (blank_line, more))
+}
+
+/// Succeeds, consuming nothing, unless only whitespace is left.
+fn more(input: &mut &str) -> PResult<()> {
+    not((multispace0, eof)).parse_next(input)
+}
+
+/// Rows of one cell per character, up to an empty line or the end of the input.
+/// Every row must be as wide as the first, and `cell` describes a valid cell.
+#[must_use]
+pub fn char_grid<'s, T: TryFrom<char>>(
+    cell: &'static str,
+) -> impl Parser<&'s str, Grid<T>, ContextError> {
+    move |input: &mut &'s str| {
+        let cut = |input: &&'s str, description| {
+            ErrMode::Cut(ContextError::new().add_context(input, expected(description)))
+        };
+
+        let mut cells = Vec::new();
+        let mut width = None;
+        loop {
+            let row_start = input.checkpoint();
+            let mut len = 0;
+            while let Some(c) = input.chars().next().filter(|c| !matches!(c, '\r' | '\n')) {
+                cells.push(T::try_from(c).map_err(|_| cut(input, cell))?);
+                input.next_token();
+                len += 1;
+            }
+            if len == 0 {
+                return Err(cut(input, cell));
+            }
+
+            let width = *width.get_or_insert(len);
+            if len != width {
+                input.reset(row_start);
+                take(len.min(width)).parse_next(input)?;
+                return Err(cut(input, "a row as wide as the first"));
+            }
+
+            let row_end = input.checkpoint();
+            if (line_ending, not(line_ending), more)
+                .parse_next(input)
+                .is_err()
+            {
+                input.reset(row_end);
+                return Ok(Grid::new(width, cells));
+            }
+        }
+    }
+}
+
+/// `NAME = (LEFT, RIGHT)`.
+pub fn node_line<'s>(input: &mut &'s str) -> PResult<(&'s str, (&'s str, &'s str))> {
+    let name = || alphanumeric1.context(expected("a node name"));
+
+    separated_pair(
+        name(),
+        tag(" = "),
+        delimited(
+            tag("("),
+            separated_pair(name(), tag(", "), name()),
+            tag(")"),
+        ),
+    )
+    .parse_next(input)
+}
+
+/// Parses `line`, the `line_idx`-th (0-based) line of the input, as a whole.
+pub fn parse_line<'s, O>(
+    line_idx: usize,
+    line: &'s str,
+    mut parser: impl Parser<&'s str, O, ContextError>,
+) -> Result<O, ParseError> {
+    parser.parse(line).map_err(|e| {
+        let mut error = locate(line, &e, "the end of the line");
+        error.line += line_idx;
+        error
+    })
+}
+
+/// Parses all of `text`, allowing trailing whitespace.
+pub fn parse_all<'s, O>(
+    text: &'s str,
+    parser: impl Parser<&'s str, O, ContextError>,
+) -> Result<O, ParseError> {
+    terminated(parser, multispace0)
+        .parse(text)
+        .map_err(|e| locate(text, &e, "the end of the input"))
+}
+
+/// Converts a winnow failure over `input` into a located [`ParseError`], expecting
+/// `fallback` if no parser said what it wanted.
+#[must_use]
+pub fn locate(
+    input: &str,
+    error: &winnow::error::ParseError<&str, ContextError>,
+    fallback: &str,
+) -> ParseError {
+    let consumed = &input[..error.offset()];
+    let line = consumed.matches('\n').count() + 1;
+    let column = consumed
+        .rsplit('\n')
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .count()
+        + 1;
+
+    let expected = error
+        .inner()
+        .context()
+        .filter_map(|context| match context {
+            StrContext::Expected(StrContextValue::StringLiteral(literal)) => {
+                Some(format!("{literal:?}"))
+            }
+            StrContext::Expected(StrContextValue::CharLiteral(c)) => Some(format!("{c:?}")),
+            StrContext::Expected(StrContextValue::Description(description)) => {
+                Some((*description).to_string())
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let expected = if expected.is_empty() {
+        fallback.to_string()
+    } else {
+        expected.join(" or ")
+    };
+
+    ParseError::new(line, column, expected)
+}
+
+#[test]
+fn test_locations() {
+    let line = "41 x8 83";
+
+    assert_eq!(
+        Err(ParseError::new(3, 4, "a number")),
+        numbers::<u32>(2, line, line)
+    );
+    assert_eq!(Ok(vec![-4, 8]), numbers(0, "-4  8", "-4  8"));
+}
+
+#[test]
+fn test_combinators() {
+    #[derive(Debug, PartialEq, Eq)]
+    struct Digit(u32);
+
+    impl TryFrom<char> for Digit {
+        type Error = ();
+
+        fn try_from(c: char) -> Result<Self, ()> {
+            c.to_digit(10).map(Self).ok_or(())
+        }
+    }
+
+    let numbers = |text| parse_all(text, labelled(tag("Time"), number_list::<u32, Vec<_>>));
+    assert_eq!(Ok(("Time", vec![7, 15, 30])), numbers("Time:   7 15  30\n"));
+    assert_eq!(
+        Err(ParseError::new(1, 10, "a number")),
+        numbers("Time: 7  1S")
+    );
+    assert_eq!(Err(ParseError::new(1, 5, "\":\"")), numbers("Time 7"));
+
+    let network = |text| parse_all(text, sections::<Vec<_>, Vec<_>>(lines(node_line)));
+    assert_eq!(
+        Ok(vec![
+            vec![("AAA", ("BBB", "CCC"))],
+            vec![("BBB", ("AAA", "ZZZ")), ("ZZZ", ("ZZZ", "ZZZ"))]
+        ]),
+        network("AAA = (BBB, CCC)\n\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)\n\n")
+    );
+    assert_eq!(
+        Err(ParseError::new(2, 16, "\")\"")),
+        network("AAA = (BBB, CCC)\nBBB = (AAA, ZZZ\n")
+    );
+
+    let grids = |text| parse_all(text, sections::<_, Vec<Grid<char>>>(char_grid("a cell")));
+    let parsed = grids("ab\ncd\n\nxyz").unwrap();
+    assert_eq!(
+        vec!["ab\ncd", "xyz"],
+        parsed.iter().map(ToString::to_string).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        Err(ParseError::new(2, 2, "a row as wide as the first")),
+        grids("ab\nc\n")
+    );
+    assert_eq!(
+        Err(ParseError::new(2, 2, "a digit")),
+        parse_all("12\n3x", char_grid::<Digit>("a digit"))
+    );
+
+    assert_eq!(
+        Err(ParseError::new(3, 4, "\" = \"")),
+        parse_line(2, "AAA - (B, C)", node_line)
+    );
+}
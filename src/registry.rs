@@ -0,0 +1,141 @@
+use std::io::BufRead;
+
+use crate::{
+    error::ParseError,
+    input::LineReader,
+    solution::{DynSolution, Solution},
+    AocError,
+};
+
+type Build = fn(Vec<String>) -> Result<Box<dyn DynSolution>, ParseError>;
+type Read = for<'r> fn(LineReader<Box<dyn BufRead + 'r>>) -> Result<Box<dyn DynSolution>, AocError>;
+
+/// A registered day: its number and constructors for its boxed solver, and for the
+/// model the REPL queries.
+pub struct Day {
+    pub number: u8,
+    build: Build,
+    read: Read,
+    model: Build,
+}
+
+impl Day {
+    pub fn build(&self, data: Vec<String>) -> Result<Box<dyn DynSolution>, ParseError> {
+        (self.build)(data)
+    }
+
+    /// Builds the solver while reading, see [`Solution::try_from_lines`].
+    pub fn read(
+        &self,
+        lines: LineReader<Box<dyn BufRead + '_>>,
+    ) -> Result<Box<dyn DynSolution>, AocError> {
+        (self.read)(lines)
+    }
+
+    /// Builds the model the REPL queries, see [`Solution::try_new_model`].
+    pub fn model(&self, data: Vec<String>) -> Result<Box<dyn DynSolution>, ParseError> {
+        (self.model)(data)
+    }
+}
+
+// Both go unused in a build without any days.
+#[allow(dead_code)]
+fn boxed<S: Solution + Send + Sync + 'static>(
+    data: Vec<String>,
+) -> Result<Box<dyn DynSolution>, ParseError> {
+    Ok(Box::new(S::try_new(data)?))
+}
+
+#[allow(dead_code)]
+fn boxed_read<S: Solution + Send + Sync + 'static>(
+    lines: LineReader<Box<dyn BufRead + '_>>,
+) -> Result<Box<dyn DynSolution>, AocError> {
+    Ok(Box::new(S::try_from_lines(lines)?))
+}
+
+/// Every day with a solution, each behind the cargo feature that compiles it in.
+/// `KNOWN` lists them all, so days left out of the build can be told apart from days
+/// that don't exist.
+macro_rules! register {
+    ($(#[cfg($feature:meta)] $day:expr => $day_mod:ident),* $(,)?) => {
+        static DAYS: &[Day] = &[
+            $(
+                #[cfg($feature)]
+                Day {
+                    number: $day,
+                    build: boxed::<crate::$day_mod::Solver>,
+                    read: boxed_read::<crate::$day_mod::Solver>,
+                    model: <crate::$day_mod::Solver as Solution>::try_new_model,
+                },
+            )*
+        ];
+
+        static KNOWN: &[u8] = &[$($day),*];
+    };
+}
+
+register! {
+    #[cfg(feature = "day-1")]
+    1 => day_1,
+    #[cfg(feature = "day-2")]
+    2 => day_2,
+    #[cfg(feature = "day-3")]
+    3 => day_3,
+    #[cfg(feature = "day-4")]
+    4 => day_4,
+    #[cfg(feature = "day-5")]
+    5 => day_5,
+    #[cfg(feature = "day-6")]
+    6 => day_6,
+    #[cfg(feature = "day-7")]
+    7 => day_7,
+    #[cfg(feature = "day-8")]
+    8 => day_8,
+    #[cfg(feature = "day-9")]
+    9 => day_9,
+    #[cfg(feature = "day-10")]
+    10 => day_10,
+}
+
+/// Every day compiled in, in ascending order.
+#[must_use]
+pub fn all() -> &'static [Day] {
+    DAYS
+}
+
+#[must_use]
+pub fn get(day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.number == day)
+}
+
+/// Like [`get`], but explains why a day is missing.
+pub fn find(day: u8) -> Result<&'static Day, String> {
+    get(day).ok_or_else(|| {
+        if KNOWN.contains(&day) {
+            format!("day {day} is not compiled in, build with --features day-{day}")
+        } else {
+            format!("day {day} is not implemented")
+        }
+    })
+}
+
+#[test]
+fn test_registry_is_ordered() {
+    assert!(all().windows(2).all(|w| w[0].number < w[1].number));
+    assert!(all().iter().all(|d| get(d.number).is_some()));
+    assert!(get(0).is_none());
+    assert_eq!(
+        Err("day 26 is not implemented".to_string()),
+        find(26).map(|_| ())
+    );
+}
+
+#[cfg(not(feature = "day-1"))]
+#[test]
+fn test_days_left_out_are_reported() {
+    assert!(get(1).is_none());
+    assert_eq!(
+        Err("day 1 is not compiled in, build with --features day-1".to_string()),
+        find(1).map(|_| ())
+    );
+}
@@ -0,0 +1,352 @@
+//! Pictures of solved puzzles: a [`Grid`] of styled characters, drawn to the
+//! terminal with ANSI colours, or to PPM and SVG images.
+//!
+//! Box-drawing characters are drawn as lines in the images, so a pipe map stays
+//! connected at any size. Any other character is a dot in a PPM, and text in an SVG.
+
+use std::{fmt::Write, ops::Range, path::Path};
+
+use crate::{
+    grid::{Grid, Point},
+    AocError,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Colour {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Colour {
+    pub const BLACK: Self = Self::rgb(0, 0, 0);
+    pub const WHITE: Self = Self::rgb(230, 230, 230);
+    pub const GREY: Self = Self::rgb(100, 100, 100);
+    pub const RED: Self = Self::rgb(220, 50, 47);
+    pub const GREEN: Self = Self::rgb(133, 200, 0);
+    pub const YELLOW: Self = Self::rgb(240, 190, 0);
+    pub const BLUE: Self = Self::rgb(38, 139, 210);
+
+    #[must_use]
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    fn hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+/// How one cell is drawn. Without a background the cell shows whatever is behind the
+/// picture: the terminal's own background, or black in images.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Style {
+    pub foreground: Colour,
+    pub background: Option<Colour>,
+}
+
+impl Style {
+    pub const PLAIN: Self = Self::fg(Colour::WHITE);
+
+    #[must_use]
+    pub const fn fg(foreground: Colour) -> Self {
+        Self {
+            foreground,
+            background: None,
+        }
+    }
+
+    #[must_use]
+    pub const fn on(self, background: Colour) -> Self {
+        Self {
+            background: Some(background),
+            ..self
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cell {
+    pub glyph: char,
+    pub style: Style,
+}
+
+impl Cell {
+    #[must_use]
+    pub const fn new(glyph: char, style: Style) -> Self {
+        Self { glyph, style }
+    }
+}
+
+pub type Canvas = Grid<Cell>;
+
+/// The box-drawing characters, with the arms each one has, in the order of
+/// [`crate::grid::ORTHOGONAL`]: up, right, down, left.
+const BOXES: [(char, [bool; 4]); 6] = [
+    ('─', [false, true, false, true]),
+    ('│', [true, false, true, false]),
+    ('┌', [false, true, true, false]),
+    ('┐', [false, false, true, true]),
+    ('└', [true, true, false, false]),
+    ('┘', [true, false, false, true]),
+];
+
+/// The box-drawing character joining the given arms, if there is one.
+#[must_use]
+pub fn box_glyph(arms: [bool; 4]) -> Option<char> {
+    BOXES
+        .iter()
+        .find_map(|&(glyph, other)| (other == arms).then_some(glyph))
+}
+
+fn arms(glyph: char) -> Option<[bool; 4]> {
+    BOXES
+        .iter()
+        .find_map(|&(other, arms)| (other == glyph).then_some(arms))
+}
+
+/// The file formats a canvas can be written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Ansi,
+    Ppm,
+    Svg,
+}
+
+impl Format {
+    /// Picks the format from the file extension. Anything but `.ppm` and `.svg` gets
+    /// the text the terminal would show.
+    #[must_use]
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("ppm") => Self::Ppm,
+            Some("svg") => Self::Svg,
+            _ => Self::Ansi,
+        }
+    }
+
+    #[must_use]
+    pub fn encode(self, canvas: &Canvas) -> Vec<u8> {
+        match self {
+            Self::Ansi => ansi(canvas).into_bytes(),
+            Self::Ppm => ppm(canvas),
+            Self::Svg => svg(canvas).into_bytes(),
+        }
+    }
+}
+
+/// Writes the canvas to `path` in the format its extension asks for, or to stdout as
+/// ANSI text for `-`.
+pub fn save(canvas: &Canvas, path: &Path) -> Result<(), AocError> {
+    if path == Path::new("-") {
+        println!("{}", ansi(canvas));
+        return Ok(());
+    }
+
+    std::fs::write(path, Format::from_path(path).encode(canvas)).map_err(|source| AocError::Io {
+        path: path.to_owned(),
+        source,
+    })
+}
+
+/// The canvas as lines of text, with escape codes switching colour only where the
+/// style changes.
+#[must_use]
+pub fn ansi(canvas: &Canvas) -> String {
+    let mut text = String::new();
+
+    for (idx, row) in canvas.rows().enumerate() {
+        if idx > 0 {
+            text.push('\n');
+        }
+        let mut current = None;
+        for cell in row {
+            if current != Some(cell.style) {
+                let Colour { r, g, b } = cell.style.foreground;
+                write!(text, "\x1b[0;38;2;{r};{g};{b}").unwrap();
+                if let Some(Colour { r, g, b }) = cell.style.background {
+                    write!(text, ";48;2;{r};{g};{b}").unwrap();
+                }
+                text.push('m');
+                current = Some(cell.style);
+            }
+            text.push(cell.glyph);
+        }
+        if current.is_some() {
+            text.push_str("\x1b[0m");
+        }
+    }
+
+    text
+}
+
+/// How many pixels wide and high a cell is in a PPM.
+const PIXELS: usize = 5;
+
+/// The canvas as a binary PPM, `PIXELS` pixels to a cell.
+#[must_use]
+pub fn ppm(canvas: &Canvas) -> Vec<u8> {
+    let (width, height) = (canvas.width() * PIXELS, canvas.height() * PIXELS);
+    let mut image = Grid::filled(width, height, Colour::BLACK);
+    let middle = PIXELS / 2;
+
+    for (point, cell) in canvas.iter() {
+        let (top, left) = (point.row * PIXELS, point.col * PIXELS);
+        let mut paint = |rows: Range<usize>, cols: Range<usize>, colour| {
+            for row in rows {
+                for col in cols.clone() {
+                    image[Point::new(top + row, left + col)] = colour;
+                }
+            }
+        };
+
+        if let Some(background) = cell.style.background {
+            paint(0..PIXELS, 0..PIXELS, background);
+        }
+        let foreground = cell.style.foreground;
+        match arms(cell.glyph) {
+            Some([up, right, down, left]) => {
+                paint(middle..middle + 1, middle..middle + 1, foreground);
+                if up {
+                    paint(0..middle, middle..middle + 1, foreground);
+                }
+                if right {
+                    paint(middle..middle + 1, middle + 1..PIXELS, foreground);
+                }
+                if down {
+                    paint(middle + 1..PIXELS, middle..middle + 1, foreground);
+                }
+                if left {
+                    paint(middle..middle + 1, 0..middle, foreground);
+                }
+            }
+            None if cell.glyph.is_whitespace() => {}
+            None => paint(1..PIXELS - 1, 1..PIXELS - 1, foreground),
+        }
+    }
+
+    let mut bytes = format!("P6\n{width} {height}\n255\n").into_bytes();
+    for pixel in image.iter().map(|(_, &pixel)| pixel) {
+        bytes.extend([pixel.r, pixel.g, pixel.b]);
+    }
+    bytes
+}
+
+/// How many SVG units wide and high a cell is.
+const UNITS: usize = 10;
+
+/// The canvas as an SVG document, with box-drawing characters as strokes and every
+/// other character as text.
+#[must_use]
+pub fn svg(canvas: &Canvas) -> String {
+    let (width, height) = (canvas.width() * UNITS, canvas.height() * UNITS);
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+         viewBox=\"0 0 {width} {height}\" font-family=\"monospace\" font-size=\"{UNITS}\" \
+         text-anchor=\"middle\" dominant-baseline=\"central\" stroke-width=\"2\" \
+         stroke-linecap=\"square\">\n<rect width=\"100%\" height=\"100%\" fill=\"{}\"/>\n",
+        Colour::BLACK.hex()
+    );
+
+    let half = UNITS / 2;
+    for (point, cell) in canvas.iter() {
+        let (x, y) = (point.col * UNITS, point.row * UNITS);
+        let (cx, cy) = (x + half, y + half);
+        let colour = cell.style.foreground.hex();
+
+        if let Some(background) = cell.style.background {
+            writeln!(
+                svg,
+                "<rect x=\"{x}\" y=\"{y}\" width=\"{UNITS}\" height=\"{UNITS}\" fill=\"{}\"/>",
+                background.hex()
+            )
+            .unwrap();
+        }
+        if let Some(arms) = arms(cell.glyph) {
+            let ends = [(cx, y), (x + UNITS, cy), (cx, y + UNITS), (x, cy)];
+            let path = arms.iter().zip(ends).filter(|(&arm, _)| arm).fold(
+                String::new(),
+                |mut path, (_, (ex, ey))| {
+                    write!(path, "M{cx} {cy}L{ex} {ey}").unwrap();
+                    path
+                },
+            );
+            writeln!(svg, "<path d=\"{path}\" stroke=\"{colour}\"/>").unwrap();
+        } else if !cell.glyph.is_whitespace() {
+            let glyph = match cell.glyph {
+                '&' => "&amp;".to_string(),
+                '<' => "&lt;".to_string(),
+                '>' => "&gt;".to_string(),
+                glyph => glyph.to_string(),
+            };
+            writeln!(
+                svg,
+                "<text x=\"{cx}\" y=\"{cy}\" fill=\"{colour}\">{glyph}</text>"
+            )
+            .unwrap();
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{ansi, box_glyph, ppm, svg, Canvas, Cell, Colour, Format, Style};
+
+    fn canvas() -> Canvas {
+        let pipe = Style::fg(Colour::GREEN);
+        Canvas::new(
+            3,
+            vec![
+                Cell::new('┌', pipe),
+                Cell::new('─', pipe),
+                Cell::new('<', Style::PLAIN.on(Colour::RED)),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_ansi() {
+        assert_eq!(
+            "\x1b[0;38;2;133;200;0m┌─\x1b[0;38;2;230;230;230;48;2;220;50;47m<\x1b[0m",
+            ansi(&canvas())
+        );
+    }
+
+    #[test]
+    fn test_images() {
+        let image = ppm(&canvas());
+        let header = b"P6\n15 5\n255\n";
+        assert_eq!(header, &image[..header.len()]);
+        assert_eq!(header.len() + 15 * 5 * 3, image.len());
+
+        // The middle row of the corner cell: its right arm and the centre are lit.
+        let pixel = |row: usize, col: usize| {
+            let at = header.len() + (row * 15 + col) * 3;
+            Colour::rgb(image[at], image[at + 1], image[at + 2])
+        };
+        assert_eq!(Colour::BLACK, pixel(2, 1));
+        assert_eq!(Colour::GREEN, pixel(2, 2));
+        assert_eq!(Colour::GREEN, pixel(2, 4));
+        assert_eq!(Colour::GREEN, pixel(3, 2));
+        assert_eq!(Colour::RED, pixel(0, 10));
+
+        let svg = svg(&canvas());
+        assert!(svg.contains("<path d=\"M5 5L10 5M5 5L5 10\" stroke=\"#85c800\"/>"));
+        assert!(svg.contains("&lt;</text>"));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!(Format::Ppm, Format::from_path(Path::new("out/loop.ppm")));
+        assert_eq!(Format::Svg, Format::from_path(Path::new("loop.svg")));
+        assert_eq!(Format::Ansi, Format::from_path(Path::new("loop.txt")));
+        assert_eq!(Some('┘'), box_glyph([true, false, false, true]));
+        assert_eq!(None, box_glyph([true, true, true, true]));
+    }
+}
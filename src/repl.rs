@@ -0,0 +1,199 @@
+//! `repl DAY`: loads a day's input once, then answers queries about it line by line.
+//!
+//! Besides the day's own queries, every session can solve a part, list and rerun
+//! earlier lines with `history` and `!N`, and list what it understands with `help`.
+
+use std::{
+    io::{self, BufRead, Write},
+    panic::{catch_unwind, AssertUnwindSafe},
+};
+
+use crate::{runner::panic_message, solution::DynSolution, ParseError, Part};
+
+const BUILTINS: [(&str, &str); 5] = [
+    ("part 1|2", "solve a part"),
+    ("history", "list the lines entered so far"),
+    (
+        "!!, !N",
+        "run the last line, or line N of the history, again",
+    ),
+    ("help", "list the queries"),
+    ("quit", "leave, as does the end of the input"),
+];
+
+/// Reads queries from `input` until it ends or says `quit`, writing a prompt before
+/// each and the answer after. Errors only come from reading and writing.
+pub fn run(
+    day: u8,
+    solver: &dyn DynSolution,
+    input: impl BufRead,
+    mut output: impl Write,
+) -> io::Result<()> {
+    let prompt = format!("day {day}> ");
+    let mut history = Vec::<String>::new();
+    let mut lines = input.lines();
+
+    loop {
+        write!(output, "{prompt}")?;
+        output.flush()?;
+        let Some(line) = lines.next().transpose()? else {
+            writeln!(output)?;
+            return Ok(());
+        };
+
+        let line = match recall(&history, line.trim()) {
+            Ok(Some(recalled)) => {
+                writeln!(output, "{recalled}")?;
+                recalled
+            }
+            Ok(None) => line.trim().to_string(),
+            Err(e) => {
+                writeln!(output, "{e}")?;
+                continue;
+            }
+        };
+
+        match line.as_str() {
+            "" => continue,
+            "quit" | "exit" => return Ok(()),
+            "help" => {
+                for (usage, description) in solver.queries().iter().chain(&BUILTINS) {
+                    writeln!(output, "  {usage:<22}{description}")?;
+                }
+                continue;
+            }
+            "history" => {
+                for (idx, entry) in history.iter().enumerate() {
+                    writeln!(output, "{:>4}  {entry}", idx + 1)?;
+                }
+                continue;
+            }
+            _ => {}
+        }
+
+        // A panicking solver only costs the one line, as it does in `run`.
+        match catch_unwind(AssertUnwindSafe(|| answer(solver, &line)))
+            .unwrap_or_else(|payload| Ok(format!("error: {}", panic_message(payload.as_ref()))))
+        {
+            Ok(answer) => writeln!(output, "{answer}")?,
+            Err(e) => {
+                // Point at the mistake in the line as it was typed after the prompt.
+                let indent = prompt.chars().count() + e.column - 1;
+                writeln!(output, "{:indent$}^ expected {}", "", e.expected)?;
+            }
+        }
+        history.push(line);
+    }
+}
+
+/// The history entry `!!` or `!N` asks for, or `None` for any other line.
+fn recall(history: &[String], line: &str) -> Result<Option<String>, String> {
+    let Some(which) = line.strip_prefix('!') else {
+        return Ok(None);
+    };
+
+    let entry = match which {
+        "!" => history.last(),
+        n => n
+            .parse::<usize>()
+            .ok()
+            .and_then(|n| history.get(n.checked_sub(1)?)),
+    };
+    entry
+        .cloned()
+        .map(Some)
+        .ok_or_else(|| format!("{line}: no such line in the history"))
+}
+
+fn answer(solver: &dyn DynSolution, line: &str) -> Result<String, ParseError> {
+    if let Some(part) = line.strip_prefix("part ") {
+        let part = part
+            .parse::<u8>()
+            .ok()
+            .and_then(|part| Part::try_from(part).ok())
+            .ok_or_else(|| ParseError::at(0, line, part, "1 or 2"))?;
+        return Ok(solver.solve(part).to_string());
+    }
+    if solver.queries().is_empty() {
+        return Err(ParseError::new(
+            1,
+            1,
+            "part 1 or part 2, this day has no queries",
+        ));
+    }
+
+    solver.query(line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::run;
+    use crate::{ParseError, Solution};
+
+    struct Panics;
+
+    impl Solution for Panics {
+        type First = u64;
+        type Second = u64;
+
+        fn try_new(_data: Vec<String>) -> Result<Self, ParseError> {
+            Ok(Self)
+        }
+
+        fn solve_first(&self) -> u64 {
+            panic!("out of cubes")
+        }
+
+        fn solve_second(&self) -> u64 {
+            2
+        }
+    }
+
+    #[test]
+    fn test_panics_are_reported() {
+        let mut output = Vec::new();
+        run(1, &Panics, &b"part 1\npart 2\n"[..], &mut output).unwrap();
+
+        assert_eq!(
+            "day 1> error: solver panicked: out of cubes\nday 1> 2\nday 1> \n",
+            String::from_utf8(output).unwrap()
+        );
+    }
+
+    #[cfg(feature = "day-8")]
+    #[test]
+    fn test_session() {
+        use crate::{day_8::Solver, Solution};
+
+        let model = Solver::try_new_model(
+            include_str!("../res/examples/day_8_a.txt")
+                .lines()
+                .map(str::to_string)
+                .collect(),
+        )
+        .unwrap();
+        let session =
+            "next AAA RL\npart 1\nnext AAX L\nnext AAA LX\n\n!1\n!9\nhistory\nquit\nnext AAA L\n";
+
+        let mut output = Vec::new();
+        run(8, &*model, session.as_bytes(), &mut output).unwrap();
+
+        assert_eq!(
+            "\
+day 8> AAA -R-> CCC -L-> ZZZ
+day 8> 2
+day 8>             ^ expected a node with a line of its own
+day 8>                  ^ expected the end of the line
+day 8> day 8> next AAA RL
+AAA -R-> CCC -L-> ZZZ
+day 8> !9: no such line in the history
+day 8>    1  next AAA RL
+   2  part 1
+   3  next AAX L
+   4  next AAA LX
+   5  next AAA RL
+day 8> ",
+            String::from_utf8(output).unwrap()
+        );
+    }
+}
@@ -0,0 +1,304 @@
+use std::{
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::Mutex,
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    json, registry,
+    render::Canvas,
+    solution::DynSolution,
+    trace::{self, Event, Level},
+    Answer, InputSource, Part,
+};
+
+/// The answer, or the reason there isn't one, for one part of one day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    pub day: u8,
+    pub part: Part,
+    pub result: Result<Answer, String>,
+    /// Time spent solving the part, not counting loading and parsing the input.
+    pub elapsed: Duration,
+    /// What the solver explained while solving, starting with what it explained while
+    /// loading the input if this is the first part solved for the day.
+    pub events: Vec<Event>,
+}
+
+impl Outcome {
+    /// The record written by `--format json`. Fields are only ever added, never
+    /// renamed or removed.
+    #[must_use]
+    pub fn to_json(&self) -> json::Value {
+        let (answer, error) = match &self.result {
+            Ok(answer) => (json::Value::Number(answer.as_i128()), json::Value::Null),
+            Err(e) => (json::Value::Null, e.as_str().into()),
+        };
+
+        json::Value::Object(vec![
+            ("day", self.day.into()),
+            ("part", self.part.number().into()),
+            ("answer", answer),
+            (
+                "elapsed_ns",
+                u64::try_from(self.elapsed.as_nanos())
+                    .unwrap_or(u64::MAX)
+                    .into(),
+            ),
+            ("error", error),
+        ])
+    }
+}
+
+/// Loads, parses and solves the requested parts of a day, turning input errors and
+/// solver panics into failed outcomes rather than aborting.
+#[must_use]
+pub fn run_day(number: u8, input: &InputSource, parts: &[Part]) -> Vec<Outcome> {
+    run_days(&[number], input, parts, 1, None)
+}
+
+/// Like [`run_day`] for several days, spreading the parsing of each day and then the
+/// solving of each part over `jobs` worker threads. Outcomes come back in day order.
+///
+/// With `explain` set, each outcome holds the events traced at that level.
+#[must_use]
+pub fn run_days(
+    days: &[u8],
+    input: &InputSource,
+    parts: &[Part],
+    jobs: usize,
+    explain: Option<Level>,
+) -> Vec<Outcome> {
+    let solvers = parallel_map(days.to_vec(), jobs, |number| {
+        let (solver, events) = trace::record(explain, || build_solver(number, input));
+        (number, solver, events)
+    });
+
+    let tasks = solvers
+        .iter()
+        .flat_map(|(number, solver, loading)| {
+            parts.iter().enumerate().map(move |(idx, &part)| {
+                let loading = if idx == 0 { loading.as_slice() } else { &[] };
+                (*number, solver, part, loading)
+            })
+        })
+        .collect::<Vec<_>>();
+
+    parallel_map(tasks, jobs, |(day, solver, part, loading)| {
+        let ((result, elapsed), events) = trace::record(explain, || match solver {
+            Ok(solver) => {
+                let start = Instant::now();
+                let result = catch_unwind(AssertUnwindSafe(|| solver.solve(part)))
+                    .map_err(|payload| panic_message(payload.as_ref()));
+                (result, start.elapsed())
+            }
+            Err(e) => (Err(e.clone()), Duration::ZERO),
+        });
+        Outcome {
+            day,
+            part,
+            result,
+            elapsed,
+            events: [loading, &events].concat(),
+        }
+    })
+}
+
+/// Loads and parses a day, then draws the solved puzzle.
+pub fn render_day(number: u8, input: &InputSource) -> Result<Canvas, String> {
+    let solver = build_solver(number, input)?;
+
+    catch_unwind(AssertUnwindSafe(|| solver.render()))
+        .map_err(|payload| panic_message(payload.as_ref()))?
+        .ok_or_else(|| "this day has no rendering".to_string())
+}
+
+/// Loads and parses a day into the model the REPL queries, turning a panicking parser
+/// into an error.
+pub fn build_model(number: u8, input: &InputSource) -> Result<Box<dyn DynSolution>, String> {
+    let day = registry::find(number)?;
+
+    catch_unwind(AssertUnwindSafe(|| {
+        input
+            .lines(number)
+            .map_err(|e| e.to_string())
+            .and_then(|lines| day.model(lines).map_err(|e| e.to_string()))
+    }))
+    .map_err(|payload| panic_message(payload.as_ref()))?
+}
+
+/// Loads and parses a day, turning a panicking parser into an error.
+fn build_solver(number: u8, input: &InputSource) -> Result<Box<dyn DynSolution>, String> {
+    let day = registry::find(number)?;
+
+    catch_unwind(AssertUnwindSafe(|| {
+        input
+            .reader(number)
+            .and_then(|lines| day.read(lines))
+            .map_err(|e| e.to_string())
+    }))
+    .map_err(|payload| panic_message(payload.as_ref()))?
+}
+
+/// Applies `f` to every item on a pool of `jobs` scoped threads, returning the results
+/// in input order. A width of 0 uses one thread per available core.
+pub fn parallel_map<T, R, F>(items: Vec<T>, jobs: usize, f: F) -> Vec<R>
+where
+    T: Send,
+    R: Send,
+    F: Fn(T) -> R + Sync,
+{
+    let jobs = match jobs {
+        0 => thread::available_parallelism().map_or(1, usize::from),
+        jobs => jobs,
+    }
+    .min(items.len());
+
+    if jobs <= 1 {
+        return items.into_iter().map(f).collect();
+    }
+
+    let count = items.len();
+    let queue = Mutex::new(items.into_iter().enumerate());
+    let results = Mutex::new((0..count).map(|_| None).collect::<Vec<_>>());
+
+    thread::scope(|scope| {
+        for _ in 0..jobs {
+            scope.spawn(|| loop {
+                let next = queue.lock().expect("work queue poisoned").next();
+                let Some((idx, item)) = next else {
+                    break;
+                };
+                let result = f(item);
+                results.lock().expect("results poisoned")[idx] = Some(result);
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .expect("results poisoned")
+        .into_iter()
+        .map(|result| result.expect("every item is processed"))
+        .collect()
+}
+
+pub(crate) fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| (*s).to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .map_or_else(
+            || "solver panicked".to_string(),
+            |message| format!("solver panicked: {message}"),
+        )
+}
+
+#[cfg(feature = "day-9")]
+#[test]
+fn test_run_day() {
+    let outcomes = run_day(9, &InputSource::Text("0 3 6 9 12 15"), &Part::BOTH);
+    assert_eq!(
+        vec![Ok(Answer::Signed(18)), Ok(Answer::Signed(-3))],
+        outcomes.into_iter().map(|o| o.result).collect::<Vec<_>>()
+    );
+
+    let outcomes = run_day(9, &InputSource::Text("0 3 x"), &[Part::First]);
+    assert_eq!(
+        Err("failed to parse input: line 1, column 5: expected a number".to_string()),
+        outcomes[0].result
+    );
+}
+
+#[cfg(feature = "day-9")]
+#[test]
+fn test_explained_run() {
+    let input = InputSource::Text("0 3 6 9 12 15\n1 3 6 10 15 21");
+    let events = |level| {
+        run_days(&[9], &input, &Part::BOTH, 1, level)
+            .into_iter()
+            .map(|o| o.events.iter().map(ToString::to_string).collect::<Vec<_>>())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(vec![Vec::<String>::new(), Vec::new()], events(None));
+    assert_eq!(
+        vec![
+            vec![
+                "history line=1 next=18 previous=-3".to_string(),
+                "history line=2 next=28 previous=0".to_string(),
+            ],
+            Vec::new(),
+        ],
+        events(Some(Level::Summary))
+    );
+    assert_eq!(
+        "  differences depth=0 row=[-3, 0, 3, 6, 9, 12, 15, 18]",
+        events(Some(Level::Step))[0][0]
+    );
+}
+
+#[cfg(feature = "day-10")]
+#[test]
+fn test_render_day() {
+    let canvas = render_day(10, &InputSource::Text(".....\n.S-7.\n.|.|.\n.L-J.\n.....")).unwrap();
+    assert_eq!((5, 5), (canvas.width(), canvas.height()));
+
+    assert_eq!(
+        Err("this day has no rendering".to_string()),
+        render_day(9, &InputSource::Text("0 3 6")).map(|_| ())
+    );
+}
+
+#[cfg(feature = "day-9")]
+#[test]
+fn test_parallel_run_matches_sequential() {
+    let input = InputSource::Text("0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45");
+
+    let results = |jobs| {
+        run_days(&[9, 9, 9], &input, &Part::BOTH, jobs, None)
+            .into_iter()
+            .map(|o| (o.day, o.part, o.result))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(results(1), results(4));
+}
+
+#[test]
+fn test_parallel_map_keeps_order_and_isolates_panics() {
+    let results = parallel_map((0..32).collect(), 4, |x: u32| {
+        catch_unwind(|| {
+            assert!(x != 7, "seven");
+            x * 2
+        })
+        .map_err(|payload| panic_message(payload.as_ref()))
+    });
+
+    assert_eq!(32, results.len());
+    assert_eq!(Ok(12), results[6]);
+    assert_eq!(Err("solver panicked: seven".to_string()), results[7]);
+    assert_eq!(Ok(16), results[8]);
+}
+
+#[test]
+fn test_outcome_json() {
+    let mut outcome = Outcome {
+        day: 8,
+        part: Part::Second,
+        result: Ok(Answer::Unsigned(13_289_612_809_129)),
+        elapsed: Duration::from_micros(1500),
+        events: Vec::new(),
+    };
+    assert_eq!(
+        r#"{"day":8,"part":2,"answer":13289612809129,"elapsed_ns":1500000,"error":null}"#,
+        outcome.to_json().to_string()
+    );
+
+    outcome.result = Err("input file res/day_8.txt not found".to_string());
+    assert_eq!(
+        r#"{"day":8,"part":2,"answer":null,"elapsed_ns":1500000,"error":"input file res/day_8.txt not found"}"#,
+        outcome.to_json().to_string()
+    );
+}
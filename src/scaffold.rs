@@ -0,0 +1,316 @@
+//! Generates a new day module from `src/day_template.rs` and wires it into the crate.
+
+use std::path::{Path, PathBuf};
+
+use crate::{answers::Answers, Answer, AocError, ParseError, Part};
+
+/// Everything needed to scaffold a day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewDay {
+    pub day: u8,
+    pub example: Vec<String>,
+    pub first: Option<Answer>,
+    pub second: Option<Answer>,
+}
+
+/// Creates `src/day_N.rs` and `res/day_N.txt` under `root` and wires the day in.
+///
+/// The module is declared in `src/lib.rs` and registered in `src/registry.rs`, both
+/// behind a new `day-N` feature in `Cargo.toml` that `all-days` turns on. An example,
+/// if given, goes into the catalog as `res/examples/day_N_a.txt`. Nothing is written if
+/// the day already exists. Returns the files that were created or changed.
+pub fn create_day(root: &Path, new_day: &NewDay) -> Result<Vec<PathBuf>, AocError> {
+    let day = new_day.day;
+    let module = format!("day_{day}");
+
+    let day_path = root.join("src").join(format!("{module}.rs"));
+    let lib_path = root.join("src").join("lib.rs");
+    let registry_path = root.join("src").join("registry.rs");
+    let manifest_path = root.join("Cargo.toml");
+    let input_path = root.join("res").join(format!("{module}.txt"));
+    let example_path = root
+        .join("res")
+        .join("examples")
+        .join(format!("{module}_a.txt"));
+
+    let template = read(&root.join("src").join("day_template.rs"))?;
+    let lib = read(&lib_path)?;
+    let registry = read(&registry_path)?;
+    let manifest = read(&manifest_path)?;
+
+    let declaration = format!("pub mod {module};");
+    if day_path.exists() || lib.lines().any(|line| line.trim() == declaration) {
+        return Err(AocError::AlreadyExists { path: day_path });
+    }
+    if registry
+        .lines()
+        .any(|line| line.trim() == format!("{day} => {module},"))
+    {
+        return Err(AocError::AlreadyExists {
+            path: registry_path,
+        });
+    }
+    if manifest
+        .lines()
+        .any(|line| line.trim() == format!("day-{day} = []"))
+    {
+        return Err(AocError::AlreadyExists {
+            path: manifest_path,
+        });
+    }
+    if !new_day.example.is_empty() && example_path.exists() {
+        return Err(AocError::AlreadyExists { path: example_path });
+    }
+    // The catalog only holds examples it can check.
+    if !new_day.example.is_empty() && new_day.first.is_none() && new_day.second.is_none() {
+        return Err(AocError::Config {
+            path: example_path.with_extension("toml"),
+            error: ParseError::new(1, 1, "an answer for the example"),
+        });
+    }
+
+    let lib = declare_module(&lib, day, &declaration);
+    let registry = register_day(&registry, day, &module).ok_or_else(|| AocError::Io {
+        path: registry_path.clone(),
+        source: std::io::Error::other("no register! block found"),
+    })?;
+    let manifest = add_feature(&manifest, day).ok_or_else(|| AocError::Io {
+        path: manifest_path.clone(),
+        source: std::io::Error::other("no all-days feature found"),
+    })?;
+
+    write(&day_path, &template.replace("{{day}}", &day.to_string()))?;
+    write(&lib_path, &lib)?;
+    write(&registry_path, &registry)?;
+    write(&manifest_path, &manifest)?;
+
+    let mut changed = vec![day_path, lib_path, registry_path, manifest_path];
+    if !input_path.exists() {
+        std::fs::create_dir_all(root.join("res")).map_err(|e| AocError::open(&input_path, e))?;
+        write(&input_path, "")?;
+        changed.push(input_path);
+    }
+
+    if !new_day.example.is_empty() {
+        let mut answers = Answers::default();
+        for (part, answer) in Part::BOTH.into_iter().zip([new_day.first, new_day.second]) {
+            if let Some(answer) = answer {
+                answers.insert(day, part, answer);
+            }
+        }
+        let answers_path = example_path.with_extension("toml");
+
+        std::fs::create_dir_all(root.join("res").join("examples"))
+            .map_err(|e| AocError::open(&example_path, e))?;
+        write(&example_path, &(new_day.example.join("\n") + "\n"))?;
+        write(&answers_path, &answers.to_string())?;
+        changed.extend([example_path, answers_path]);
+    }
+
+    Ok(changed)
+}
+
+fn read(path: &Path) -> Result<String, AocError> {
+    std::fs::read_to_string(path).map_err(|e| AocError::open(path, e))
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), AocError> {
+    std::fs::write(path, contents).map_err(|e| AocError::open(path, e))
+}
+
+fn feature_gate(day: u8) -> String {
+    format!("#[cfg(feature = \"day-{day}\")]")
+}
+
+/// Splits lines into entries, each ending with a line that `closes` accepts and
+/// starting with the attributes above it.
+fn entries(
+    lines: impl IntoIterator<Item = String>,
+    closes: impl Fn(&str) -> bool,
+) -> Vec<Vec<String>> {
+    let mut entries = Vec::new();
+    let mut entry = Vec::new();
+    for line in lines {
+        let last = closes(&line);
+        entry.push(line);
+        if last {
+            entries.push(std::mem::take(&mut entry));
+        }
+    }
+    if !entry.is_empty() {
+        entries.push(entry);
+    }
+    entries
+}
+
+/// Adds `declaration`, behind the day's feature, to the block of `pub mod day_N;`
+/// lines, keeping the order rustfmt would give it.
+fn declare_module(lib: &str, day: u8, declaration: &str) -> String {
+    let mut lines = lib.lines().map(str::to_string).collect::<Vec<_>>();
+
+    let is_day = |line: &String| {
+        line.starts_with("pub mod day_") || line.starts_with("#[cfg(feature = \"day-")
+    };
+    let start = lines.iter().position(is_day).unwrap_or(lines.len());
+    let end = lines[start..]
+        .iter()
+        .position(|line| !is_day(line))
+        .map_or(lines.len(), |offset| start + offset);
+
+    let mut modules = entries(lines.drain(start..end), |line| line.starts_with("pub mod"));
+    modules.push(vec![feature_gate(day), declaration.to_string()]);
+    modules.sort_by_cached_key(|entry| {
+        entry
+            .last()
+            .map(|line| line.trim_end_matches(';').to_string())
+    });
+    lines.splice(start..start, modules.into_iter().flatten());
+
+    lines.join("\n") + "\n"
+}
+
+/// Adds `day => module,`, behind the day's feature, to the `register!` block, keeping
+/// it in day order.
+fn register_day(registry: &str, day: u8, module: &str) -> Option<String> {
+    let mut lines = registry.lines().map(str::to_string).collect::<Vec<_>>();
+
+    let start = lines.iter().position(|line| line == "register! {")? + 1;
+    let end = start + lines[start..].iter().position(|line| line == "}")?;
+
+    let entry_day = |entry: &Vec<String>| {
+        entry
+            .last()?
+            .trim()
+            .split_once(" => ")
+            .and_then(|(day, _)| day.parse::<u8>().ok())
+    };
+    let mut days = entries(lines.drain(start..end), |line| line.contains(" => "));
+    days.push(vec![
+        format!("    {}", feature_gate(day)),
+        format!("    {day} => {module},"),
+    ]);
+    days.sort_by_key(entry_day);
+    lines.splice(start..start, days.into_iter().flatten());
+
+    Some(lines.join("\n") + "\n")
+}
+
+/// Adds the `day-N = []` feature to `Cargo.toml` and turns it on in `all-days`, both
+/// in day order.
+fn add_feature(manifest: &str, day: u8) -> Option<String> {
+    let mut lines = manifest.lines().map(str::to_string).collect::<Vec<_>>();
+
+    let start = lines.iter().position(|line| line == "all-days = [")? + 1;
+    let end = start + lines[start..].iter().position(|line| line == "]")?;
+    let enabled_day = |line: &String| {
+        line.trim()
+            .strip_prefix("\"day-")?
+            .strip_suffix("\",")?
+            .parse::<u8>()
+            .ok()
+    };
+    let position = lines[start..end]
+        .iter()
+        .position(|line| enabled_day(line).is_some_and(|other| other > day))
+        .map_or(end, |offset| start + offset);
+    lines.insert(position, format!("    \"day-{day}\","));
+
+    let defined_day = |line: &String| {
+        line.strip_prefix("day-")?
+            .strip_suffix(" = []")?
+            .parse::<u8>()
+            .ok()
+    };
+    // After the feature for the closest earlier day, or straight after `all-days`.
+    let position = lines
+        .iter()
+        .rposition(|line| defined_day(line).is_some_and(|other| other < day))
+        .map_or(end + 2, |idx| idx + 1);
+    lines.insert(position, format!("day-{day} = []"));
+
+    Some(lines.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{create_day, NewDay};
+    use crate::{answers::Answers, examples::discover, Answer, AocError, Part};
+
+    #[test]
+    fn test_create_day() {
+        let root = std::env::temp_dir().join(format!("aoc_2023_scaffold_{}", std::process::id()));
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("Cargo.toml"), include_str!("../Cargo.toml")).unwrap();
+        for (name, contents) in [
+            ("day_template.rs", include_str!("day_template.rs")),
+            ("lib.rs", include_str!("lib.rs")),
+            ("registry.rs", include_str!("registry.rs")),
+        ] {
+            std::fs::write(root.join("src").join(name), contents).unwrap();
+        }
+
+        let new_day = NewDay {
+            day: 11,
+            example: vec!["...#".to_string(), String::new(), "#...".to_string()],
+            first: Some(Answer::Unsigned(374)),
+            second: None,
+        };
+        create_day(&root, &new_day).unwrap();
+
+        let day = std::fs::read_to_string(root.join("src/day_11.rs")).unwrap();
+        assert!(day.starts_with("// https://adventofcode.com/2023/day/11\n"));
+
+        let example = std::fs::read_to_string(root.join("res/examples/day_11_a.txt")).unwrap();
+        assert_eq!("...#\n\n#...\n", example);
+        let answers = Answers::load(root.join("res/examples/day_11_a.toml")).unwrap();
+        assert_eq!(Some(Answer::Unsigned(374)), answers.get(11, Part::First));
+        assert_eq!(None, answers.get(11, Part::Second));
+
+        let lib = std::fs::read_to_string(root.join("src/lib.rs")).unwrap();
+        assert!(lib.contains(
+            "pub mod day_10;\n#[cfg(feature = \"day-11\")]\npub mod day_11;\n#[cfg(feature = \"day-2\")]\n"
+        ));
+
+        let registry = std::fs::read_to_string(root.join("src/registry.rs")).unwrap();
+        assert!(registry
+            .contains("    10 => day_10,\n    #[cfg(feature = \"day-11\")]\n    11 => day_11,\n}"));
+
+        let manifest = std::fs::read_to_string(root.join("Cargo.toml")).unwrap();
+        assert!(manifest.contains("    \"day-10\",\n    \"day-11\",\n]\n"));
+        assert!(manifest.contains("day-10 = []\nday-11 = []\n\n"));
+
+        assert!(root.join("res/day_11.txt").exists());
+
+        let examples = discover(root.join("res/examples")).unwrap();
+        assert_eq!(1, examples.len());
+        assert_eq!(11, examples[0].day);
+
+        assert!(matches!(
+            create_day(&root, &new_day),
+            Err(AocError::AlreadyExists { .. })
+        ));
+        assert!(matches!(
+            create_day(
+                &root,
+                &NewDay {
+                    day: 3,
+                    ..new_day.clone()
+                }
+            ),
+            Err(AocError::AlreadyExists { .. })
+        ));
+
+        let unanswered = NewDay {
+            day: 12,
+            first: None,
+            ..new_day
+        };
+        assert!(matches!(
+            create_day(&root, &unanswered),
+            Err(AocError::Config { .. })
+        ));
+        assert!(!root.join("src/day_12.rs").exists());
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
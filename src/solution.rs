@@ -0,0 +1,265 @@
+use std::{fmt::Display, io::BufRead};
+
+use crate::{error::ParseError, input::LineReader, render::Canvas, AocError};
+
+/// Which half of a day's puzzle to solve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    First,
+    Second,
+}
+
+impl Part {
+    pub const BOTH: [Self; 2] = [Self::First, Self::Second];
+
+    #[must_use]
+    pub const fn number(self) -> u8 {
+        match self {
+            Self::First => 1,
+            Self::Second => 2,
+        }
+    }
+}
+
+impl TryFrom<u8> for Part {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(Self::First),
+            2 => Ok(Self::Second),
+            x => Err(format!("{x} is not a valid part")),
+        }
+    }
+}
+
+/// A puzzle answer, normalised across the integer types the solvers return.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Answer {
+    Unsigned(u64),
+    Signed(i64),
+}
+
+impl Answer {
+    #[must_use]
+    pub fn as_i128(self) -> i128 {
+        match self {
+            Self::Unsigned(x) => i128::from(x),
+            Self::Signed(x) => i128::from(x),
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unsigned(x) => write!(f, "{x}"),
+            Self::Signed(x) => write!(f, "{x}"),
+        }
+    }
+}
+
+impl std::str::FromStr for Answer {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse()
+            .map(Self::Unsigned)
+            .or_else(|_| s.parse().map(Self::Signed))
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(value: u32) -> Self {
+        Self::Unsigned(u64::from(value))
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(value: u64) -> Self {
+        Self::Unsigned(value)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Self::Unsigned(value as u64)
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Self {
+        Self::Signed(value)
+    }
+}
+
+/// A day's puzzle, built from its input lines.
+pub trait Solution {
+    type First: Into<Answer> + Display;
+    type Second: Into<Answer> + Display;
+
+    /// The queries [`Solution::query`] understands, each as its usage and what it
+    /// answers.
+    const QUERIES: &'static [(&'static str, &'static str)] = &[];
+
+    fn try_new(data: Vec<String>) -> Result<Self, ParseError>
+    where
+        Self: Sized;
+
+    /// Like [`Solution::try_new`], but panics on malformed input.
+    #[must_use]
+    fn new(data: Vec<String>) -> Self
+    where
+        Self: Sized,
+    {
+        Self::try_new(data).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Builds the solver from a line reader. The default collects every line and hands
+    /// them to [`Solution::try_new`]; days implementing [`LineSolution`] override it
+    /// with [`LineSolution::stream`] to parse as they read.
+    fn try_from_lines<R: BufRead>(lines: LineReader<R>) -> Result<Self, AocError>
+    where
+        Self: Sized,
+    {
+        Ok(Self::try_new(lines.collect()?)?)
+    }
+
+    fn try_from_reader(reader: impl BufRead) -> Result<Self, AocError>
+    where
+        Self: Sized,
+    {
+        Self::try_from_lines(LineReader::new("<input>", reader))
+    }
+
+    fn try_from_str(text: &str) -> Result<Self, ParseError>
+    where
+        Self: Sized,
+    {
+        match Self::try_from_reader(text.as_bytes()) {
+            Ok(solver) => Ok(solver),
+            Err(AocError::Parse(e)) => Err(e),
+            Err(e) => unreachable!("reading from a string failed: {e}"),
+        }
+    }
+
+    fn solve_first(&self) -> Self::First;
+
+    fn solve_second(&self) -> Self::Second;
+
+    /// A picture of the puzzle and how it was solved, for days that have one.
+    fn render(&self) -> Option<Canvas> {
+        None
+    }
+
+    /// Builds what the REPL answers queries from: the solver itself, unless it keeps
+    /// too little of the input to answer them, as a streaming solver does.
+    fn try_new_model(data: Vec<String>) -> Result<Box<dyn DynSolution>, ParseError>
+    where
+        Self: Sized + Send + Sync + 'static,
+    {
+        Ok(Box::new(Self::try_new(data)?))
+    }
+
+    /// Answers one of [`Solution::QUERIES`] about the parsed input. A query that
+    /// can't be answered is reported as a parse error on line 1.
+    fn query(&self, query: &str) -> Result<String, ParseError> {
+        let _ = query;
+        Err(ParseError::new(1, 1, "a query, but this day has none"))
+    }
+}
+
+/// A solver folded together one line at a time, keeping only what its answers need
+/// rather than the input itself.
+pub trait LineSolution: Default {
+    fn push_line(&mut self, line_idx: usize, line: &str) -> Result<(), ParseError>;
+
+    fn from_strs<'a>(lines: impl IntoIterator<Item = &'a str>) -> Result<Self, ParseError> {
+        let mut solver = Self::default();
+        for (line_idx, line) in lines.into_iter().enumerate() {
+            solver.push_line(line_idx, line)?;
+        }
+        Ok(solver)
+    }
+
+    fn stream<R: BufRead>(mut lines: LineReader<R>) -> Result<Self, AocError> {
+        let mut solver = Self::default();
+        while let Some((line_idx, line)) = lines.next_line()? {
+            solver.push_line(line_idx, line)?;
+        }
+        Ok(solver)
+    }
+}
+
+/// Object-safe view of a [`Solution`], used by the registry to run days generically.
+/// Solvers are shared between worker threads when parts run in parallel.
+pub trait DynSolution: Send + Sync {
+    fn solve(&self, part: Part) -> Answer;
+
+    fn render(&self) -> Option<Canvas>;
+
+    fn queries(&self) -> &'static [(&'static str, &'static str)];
+
+    fn query(&self, query: &str) -> Result<String, ParseError>;
+}
+
+impl<S: Solution + Send + Sync> DynSolution for S {
+    fn solve(&self, part: Part) -> Answer {
+        match part {
+            Part::First => self.solve_first().into(),
+            Part::Second => self.solve_second().into(),
+        }
+    }
+
+    fn render(&self) -> Option<Canvas> {
+        Solution::render(self)
+    }
+
+    fn queries(&self) -> &'static [(&'static str, &'static str)] {
+        S::QUERIES
+    }
+
+    fn query(&self, query: &str) -> Result<String, ParseError> {
+        Solution::query(self, query)
+    }
+}
+
+#[cfg(feature = "day-9")]
+#[test]
+fn test_stream_generated_input() {
+    use std::io::{BufReader, Read};
+
+    /// Yields `remaining` copies of one line without ever holding more than one.
+    struct Generated {
+        line: &'static [u8],
+        offset: usize,
+        remaining: usize,
+    }
+
+    impl Read for Generated {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.remaining == 0 {
+                return Ok(0);
+            }
+            let chunk = &self.line[self.offset..];
+            let len = chunk.len().min(buf.len());
+            buf[..len].copy_from_slice(&chunk[..len]);
+            self.offset += len;
+            if self.offset == self.line.len() {
+                self.offset = 0;
+                self.remaining -= 1;
+            }
+            Ok(len)
+        }
+    }
+
+    let reader = BufReader::new(Generated {
+        line: b"0 3 6 9 12 15\n",
+        offset: 0,
+        remaining: 20_000,
+    });
+    let solver = crate::day_9::Solver::try_from_reader(reader).unwrap();
+
+    assert_eq!(18 * 20_000, solver.solve_first());
+    assert_eq!(-3 * 20_000, solver.solve_second());
+}
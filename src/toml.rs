@@ -0,0 +1,98 @@
+//! The small TOML subset the answers and the ledger are kept in: `[[name]]` headers,
+//! each followed by `key = value` lines, with `#` comments.
+//!
+//! Tables are read without knowing their keys, so each file checks its own while
+//! errors still point at the line and column of the offending key or value.
+
+use std::str::FromStr;
+
+use crate::{parsers, ParseError, Part};
+
+/// One `[[name]]` table and its fields, in the order they were written.
+pub struct Table<'a> {
+    line_idx: usize,
+    pub fields: Vec<Field<'a>>,
+}
+
+impl Table<'_> {
+    /// The error for a key the table should have had.
+    #[must_use]
+    pub fn missing(&self, key: &str) -> ParseError {
+        ParseError::new(self.line_idx + 1, 1, format!("a {key} key"))
+    }
+}
+
+/// A `key = value` line, with the line kept to locate errors.
+pub struct Field<'a> {
+    line_idx: usize,
+    line: &'a str,
+    pub key: &'a str,
+    pub value: &'a str,
+}
+
+impl Field<'_> {
+    pub fn number<T: FromStr>(&self) -> Result<T, ParseError> {
+        parsers::number(self.line_idx, self.line, self.value)
+    }
+
+    pub fn part(&self) -> Result<Part, ParseError> {
+        Part::try_from(self.number::<u8>()?).map_err(|_| self.invalid("1 or 2"))
+    }
+
+    /// The text between the value's double quotes, if it is quoted.
+    #[must_use]
+    pub fn string(&self) -> Option<&str> {
+        self.value.strip_prefix('"')?.strip_suffix('"')
+    }
+
+    /// An error pointing at the value.
+    #[must_use]
+    pub fn invalid(&self, expected: &str) -> ParseError {
+        ParseError::at(self.line_idx, self.line, self.value, expected)
+    }
+
+    /// An error pointing at the key, naming the keys that would have been accepted.
+    #[must_use]
+    pub fn unknown(&self, keys: &str) -> ParseError {
+        ParseError::at(self.line_idx, self.line, self.key, keys)
+    }
+}
+
+/// Every `[[name]]` table in `text`. Anything but a table, its fields, comments and
+/// blank lines is an error.
+pub fn tables<'a>(text: &'a str, name: &str) -> Result<Vec<Table<'a>>, ParseError> {
+    let header = format!("[[{name}]]");
+    let mut tables = Vec::<Table>::new();
+
+    for (line_idx, line) in text.lines().enumerate() {
+        let content = line.split_once('#').map_or(line, |(content, _)| content);
+        let content = content.trim();
+
+        if content.is_empty() {
+            continue;
+        }
+        if content == header {
+            tables.push(Table {
+                line_idx,
+                fields: Vec::new(),
+            });
+            continue;
+        }
+
+        let table = tables
+            .last_mut()
+            .ok_or_else(|| ParseError::at(line_idx, line, content, format!("{header:?}")))?;
+
+        let (key, value) = content
+            .split_once('=')
+            .ok_or_else(|| ParseError::at(line_idx, line, content, "key = value"))?;
+        table.fields.push(Field {
+            line_idx,
+            line,
+            key: key.trim(),
+            value: value.trim(),
+        });
+    }
+
+    Ok(tables)
+}
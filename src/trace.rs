@@ -0,0 +1,164 @@
+//! Opt-in events describing how a solver reached its answer, for `--explain`.
+//!
+//! Solvers emit events with [`trace!`](crate::trace!), which does nothing but check a
+//! thread-local level unless [`record`] is collecting events on the same thread. The
+//! event's fields are only evaluated once the level check passes.
+
+use std::{cell::RefCell, fmt::Display};
+
+/// How much detail an event adds. Recording at a level keeps the events at it and
+/// every level above.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    /// A handful of events per part, such as one per input line.
+    Summary = 1,
+    /// Each step of the work, such as every layer a value goes through.
+    Step = 2,
+    /// Everything, including each node visited by a walk.
+    Detail = 3,
+}
+
+impl TryFrom<u8> for Level {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(Self::Summary),
+            2 => Ok(Self::Step),
+            3 => Ok(Self::Detail),
+            x => Err(format!("{x} is not a valid verbosity")),
+        }
+    }
+}
+
+/// One thing a solver did, with named values in the order they were given.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    pub level: Level,
+    pub name: &'static str,
+    pub fields: Vec<(&'static str, String)>,
+}
+
+/// `name key=value ...`, indented by how detailed the event is.
+impl Display for Event {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let indent = 2 * (self.level as usize - 1);
+        write!(f, "{:indent$}{}", "", self.name)?;
+        for (key, value) in &self.fields {
+            write!(f, " {key}={value}")?;
+        }
+        Ok(())
+    }
+}
+
+struct Recorder {
+    level: Level,
+    events: Vec<Event>,
+}
+
+thread_local! {
+    static RECORDER: RefCell<Option<Recorder>> = const { RefCell::new(None) };
+}
+
+/// Whether an event at `level` would be recorded. Used by [`trace!`](crate::trace!).
+#[must_use]
+pub fn enabled(level: Level) -> bool {
+    RECORDER.with_borrow(|recorder| recorder.as_ref().is_some_and(|r| level <= r.level))
+}
+
+/// Keeps an event for the surrounding [`record`]. Used by [`trace!`](crate::trace!).
+pub fn emit(event: Event) {
+    RECORDER.with_borrow_mut(|recorder| {
+        if let Some(recorder) = recorder {
+            recorder.events.push(event);
+        }
+    });
+}
+
+/// Runs `f`, collecting the events it emits on this thread at `level` and above. With
+/// no level nothing is collected, and tracing costs only the checks.
+pub fn record<R>(level: Option<Level>, f: impl FnOnce() -> R) -> (R, Vec<Event>) {
+    // Restores whatever was recording before, even if `f` panics.
+    struct Restore(Option<Recorder>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            RECORDER.set(self.0.take());
+        }
+    }
+
+    let Some(level) = level else {
+        return (f(), Vec::new());
+    };
+
+    let outer = RECORDER.replace(Some(Recorder {
+        level,
+        events: Vec::new(),
+    }));
+    let restore = Restore(outer);
+    let result = f();
+    let events = RECORDER
+        .take()
+        .map(|recorder| recorder.events)
+        .unwrap_or_default();
+    drop(restore);
+
+    (result, events)
+}
+
+/// Emits an event with [`Debug`]-formatted fields, which are only evaluated when the
+/// event is recorded:
+///
+/// ```text
+/// trace!(Level::Detail, "visit", node = name, step = steps);
+/// ```
+#[macro_export]
+macro_rules! trace {
+    ($level:expr, $name:literal $(, $key:ident = $value:expr)* $(,)?) => {
+        if $crate::trace::enabled($level) {
+            $crate::trace::emit($crate::trace::Event {
+                level: $level,
+                name: $name,
+                fields: vec![$((stringify!($key), format!("{:?}", $value))),*],
+            });
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{record, Level};
+
+    #[test]
+    fn test_record() {
+        let evaluated = std::cell::Cell::new(0);
+        let field = || {
+            evaluated.set(evaluated.get() + 1);
+            evaluated.get()
+        };
+        let emit = || {
+            crate::trace!(Level::Summary, "summary", n = field());
+            crate::trace!(Level::Detail, "detail", n = field(), name = "x");
+            7
+        };
+
+        assert_eq!((7, Vec::new()), record(None, emit));
+        assert_eq!(0, evaluated.get());
+
+        let (_, events) = record(Some(Level::Step), emit);
+        assert_eq!(
+            vec!["summary n=1"],
+            events.iter().map(ToString::to_string).collect::<Vec<_>>()
+        );
+
+        let (_, events) = record(Some(Level::Detail), || {
+            let (_, inner) = record(Some(Level::Summary), emit);
+            assert_eq!(1, inner.len());
+            emit()
+        });
+        assert_eq!(
+            vec!["summary n=3", "    detail n=4 name=\"x\""],
+            events.iter().map(ToString::to_string).collect::<Vec<_>>()
+        );
+        assert!(!super::enabled(Level::Summary));
+    }
+}
@@ -0,0 +1,326 @@
+//! `watch N`: solves a day again whenever its input or examples change, beside the
+//! answers from the time before.
+//!
+//! Files are polled with nothing but std: each poll hashes every watched file, and a
+//! change is only acted on once the files have stopped changing for a while, so an
+//! editor saving in several writes triggers a single run.
+
+use std::{
+    collections::{hash_map::DefaultHasher, BTreeMap, BTreeSet},
+    fmt::Write,
+    hash::{Hash, Hasher},
+    path::PathBuf,
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{examples::Example, runner, Answer, InputSource, Part};
+
+/// The hash of each watched file's contents, or `None` for a file that can't be read.
+type Snapshot = BTreeMap<PathBuf, Option<u64>>;
+
+/// Polls a day's input and examples for changes.
+pub struct Watcher {
+    day: u8,
+    input: InputSource<'static>,
+    examples: PathBuf,
+    interval: Duration,
+    settle: Duration,
+    seen: Snapshot,
+}
+
+impl Watcher {
+    /// Watches the day's file in `input`, and every `day_N_*` file in the `examples`
+    /// directory, including ones added later. What they hold now counts as seen.
+    #[must_use]
+    pub fn new(day: u8, input: InputSource<'static>, examples: impl Into<PathBuf>) -> Self {
+        let mut watcher = Self {
+            day,
+            input,
+            examples: examples.into(),
+            interval: Duration::from_millis(250),
+            settle: Duration::from_millis(500),
+            seen: Snapshot::new(),
+        };
+        watcher.seen = watcher.snapshot();
+        watcher
+    }
+
+    /// How often the files are read, and how long they must stay the same after a
+    /// change before it is reported.
+    #[must_use]
+    pub const fn with_timing(mut self, interval: Duration, settle: Duration) -> Self {
+        self.interval = interval;
+        self.settle = settle;
+        self
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        let prefix = format!("day_{}_", self.day);
+        let examples = std::fs::read_dir(&self.examples)
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| Some(entry.ok()?.path()))
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with(&prefix))
+            });
+
+        self.input
+            .path(self.day)
+            .into_iter()
+            .chain(examples)
+            .collect()
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        self.files()
+            .into_iter()
+            .map(|path| {
+                let hash = std::fs::read(&path).ok().map(|contents| {
+                    let mut hasher = DefaultHasher::new();
+                    contents.hash(&mut hasher);
+                    hasher.finish()
+                });
+                (path, hash)
+            })
+            .collect()
+    }
+
+    /// Blocks until the watched files change and then settle, returning the files
+    /// that differ from the last time. Files changed and changed back are ignored.
+    pub fn wait(&mut self) -> Vec<PathBuf> {
+        loop {
+            thread::sleep(self.interval);
+            let mut current = self.snapshot();
+            if current == self.seen {
+                continue;
+            }
+
+            let mut quiet_since = Instant::now();
+            while quiet_since.elapsed() < self.settle {
+                thread::sleep(self.interval);
+                let next = self.snapshot();
+                if next != current {
+                    current = next;
+                    quiet_since = Instant::now();
+                }
+            }
+
+            let changed = current
+                .keys()
+                .chain(self.seen.keys())
+                .filter(|&path| current.get(path) != self.seen.get(path))
+                .cloned()
+                .collect::<BTreeSet<_>>();
+            self.seen = current;
+            if !changed.is_empty() {
+                return changed.into_iter().collect();
+            }
+        }
+    }
+
+    /// Solves both parts of the input, and the parts of each example with an answer.
+    #[must_use]
+    pub fn run(&self) -> Run {
+        let mut run = Run::default();
+
+        let outcomes = runner::run_day(self.day, &self.input, &Part::BOTH);
+        run.entries
+            .extend(outcomes.into_iter().map(|outcome| Entry {
+                source: "input".to_string(),
+                part: outcome.part,
+                result: outcome.result,
+                elapsed: outcome.elapsed,
+                expected: None,
+            }));
+
+        let mut paths = self
+            .files()
+            .into_iter()
+            .filter(|path| path.parent() == Some(&self.examples))
+            .filter(|path| path.extension().is_some_and(|extension| extension == "txt"))
+            .collect::<Vec<_>>();
+        paths.sort();
+
+        for path in paths {
+            match Example::load(&path) {
+                Ok(example) => run.entries.extend(example_entries(&example)),
+                Err(e) => run.errors.push(e.to_string()),
+            }
+        }
+
+        run
+    }
+}
+
+fn example_entries(example: &Example) -> impl Iterator<Item = Entry> + '_ {
+    let source = example
+        .path
+        .file_stem()
+        .map_or_else(String::new, |stem| stem.to_string_lossy().into_owned());
+    let parts = Part::BOTH
+        .into_iter()
+        .filter(|&part| example.answers.get(example.day, part).is_some())
+        .collect::<Vec<_>>();
+
+    runner::run_day(
+        example.day,
+        &InputSource::Path(example.path.clone()),
+        &parts,
+    )
+    .into_iter()
+    .map(move |outcome| Entry {
+        source: source.clone(),
+        part: outcome.part,
+        expected: example.answers.get(outcome.day, outcome.part),
+        result: outcome.result,
+        elapsed: outcome.elapsed,
+    })
+}
+
+/// One part solved for the input or for an example.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// `input`, or the example's file name without the extension.
+    pub source: String,
+    pub part: Part,
+    pub result: Result<Answer, String>,
+    pub elapsed: Duration,
+    pub expected: Option<Answer>,
+}
+
+impl Entry {
+    fn answer(&self) -> String {
+        match &self.result {
+            Ok(answer) => answer.to_string(),
+            Err(e) => format!("error: {e}"),
+        }
+    }
+}
+
+/// Everything solved after one change, and the examples that couldn't be loaded.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Run {
+    pub entries: Vec<Entry>,
+    pub errors: Vec<String>,
+}
+
+impl Run {
+    /// A line per entry with its answer and timing, giving the answer from `previous`
+    /// wherever it differs, and whether each example got its expected answer.
+    #[must_use]
+    pub fn report(&self, previous: Option<&Self>) -> String {
+        let mut report = String::new();
+
+        for entry in &self.entries {
+            let mut answer = entry.answer();
+            let before = previous.and_then(|previous| {
+                previous
+                    .entries
+                    .iter()
+                    .find(|other| (&other.source, other.part) == (&entry.source, entry.part))
+            });
+            if let Some(before) = before.filter(|before| before.result != entry.result) {
+                write!(answer, " (was {})", before.answer()).unwrap();
+            }
+
+            let verdict = match (entry.expected, &entry.result) {
+                (None, _) => String::new(),
+                (Some(expected), Ok(actual)) if expected.as_i128() == actual.as_i128() => {
+                    "  pass".to_string()
+                }
+                (Some(expected), _) => format!("  FAIL, expected {expected}"),
+            };
+
+            writeln!(
+                report,
+                "  {:<12} {}  {:>8}  {answer}{verdict}",
+                entry.source,
+                entry.part.number(),
+                format!("{:.1?}", entry.elapsed)
+            )
+            .unwrap();
+        }
+        for error in &self.errors {
+            writeln!(report, "  {error}").unwrap();
+        }
+
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Entry, Run, Watcher};
+    use crate::{Answer, InputSource, Part};
+
+    fn entry(source: &str, part: Part, answer: u64, expected: Option<u64>) -> Entry {
+        Entry {
+            source: source.to_string(),
+            part,
+            result: Ok(Answer::Unsigned(answer)),
+            elapsed: Duration::from_micros(1500),
+            expected: expected.map(Answer::Unsigned),
+        }
+    }
+
+    #[test]
+    fn test_report() {
+        let before = Run {
+            entries: vec![
+                entry("input", Part::First, 20, None),
+                entry("day_8_a", Part::First, 2, Some(2)),
+            ],
+            errors: Vec::new(),
+        };
+        let after = Run {
+            entries: vec![
+                entry("input", Part::First, 21, None),
+                entry("day_8_a", Part::First, 2, Some(2)),
+                entry("day_8_b", Part::First, 5, Some(6)),
+            ],
+            errors: vec!["bad example".to_string()],
+        };
+
+        assert_eq!(
+            "  input        1     1.5ms  21 (was 20)\n  \
+             day_8_a      1     1.5ms  2  pass\n  \
+             day_8_b      1     1.5ms  5  FAIL, expected 6\n  \
+             bad example\n",
+            after.report(Some(&before))
+        );
+    }
+
+    #[test]
+    fn test_wait() {
+        let dir = std::env::temp_dir().join(format!("aoc_2023_watch_{}", std::process::id()));
+        let examples = dir.join("examples");
+        std::fs::create_dir_all(&examples).unwrap();
+        std::fs::write(dir.join("day_8.txt"), "LR\n").unwrap();
+        std::fs::write(examples.join("day_9_a.txt"), "1 2 3\n").unwrap();
+
+        let mut watcher = Watcher::new(8, InputSource::Dir(dir.clone()), &examples)
+            .with_timing(Duration::from_millis(5), Duration::from_millis(20));
+
+        // Only the day's own files count, and a burst of writes is one change.
+        std::fs::write(examples.join("day_9_a.txt"), "2 3 4\n").unwrap();
+        for directions in ["L", "LL", "LLR"] {
+            std::fs::write(dir.join("day_8.txt"), directions).unwrap();
+            std::thread::sleep(Duration::from_millis(2));
+        }
+        std::fs::write(examples.join("day_8_z.txt"), "RL\n").unwrap();
+
+        let mut changed = watcher.wait();
+        changed.sort();
+        assert_eq!(
+            vec![dir.join("day_8.txt"), dir.join("examples/day_8_z.txt")],
+            changed
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
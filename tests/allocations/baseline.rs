@@ -0,0 +1,9 @@
+//! The owned parsers of days 2, 4, 7 and 8 as they were before they borrowed from
+//! their input, copied unchanged apart from their tests.
+
+#![allow(dead_code)]
+
+pub mod day_2;
+pub mod day_4;
+pub mod day_7;
+pub mod day_8;
@@ -0,0 +1,83 @@
+pub struct Solver {
+    data: Vec<Card>,
+}
+
+#[derive(Debug)]
+struct Card {
+    winning_values: Vec<u32>,
+    all_values: Vec<u32>,
+}
+
+impl Card {
+    fn get_matches(&self) -> usize {
+        self.winning_values
+            .iter()
+            .filter(|w| self.all_values.contains(w))
+            .count()
+    }
+}
+
+impl Solver {
+    #[must_use]
+    pub fn new(data: Vec<String>) -> Self {
+        let mut cards = Vec::new();
+
+        for card_line in data {
+            let Some((_, right)) = card_line.split_once(": ") else {
+                panic!("Splitting card info")
+            };
+
+            let Some((winning, all)) = right.split_once(" | ") else {
+                panic!("Splitting card numbers")
+            };
+
+            let winning = winning
+                .split_whitespace()
+                .filter_map(|v| v.parse::<u32>().ok())
+                .collect::<Vec<_>>();
+
+            let all = all
+                .split_whitespace()
+                .filter_map(|v| v.parse::<u32>().ok())
+                .collect::<Vec<_>>();
+
+            let card = Card {
+                winning_values: winning,
+                all_values: all,
+            };
+
+            cards.push(card);
+        }
+
+        Self { data: cards }
+    }
+
+    #[must_use]
+    pub fn solve_first(&self) -> usize {
+        self.data
+            .iter()
+            .map(Card::get_matches)
+            .filter(|matches| *matches != 0)
+            .fold(0, |acc, matches| {
+                acc + 2_usize.pow(matches.saturating_sub(1) as u32)
+            })
+    }
+
+    #[must_use]
+    pub fn solve_second(&self) -> u32 {
+        let total_cards = self.data.len();
+
+        let mut card_copies = vec![1; total_cards];
+
+        for (idx, matches) in self.data.iter().map(Card::get_matches).enumerate() {
+            let num_copies = *card_copies.get(idx).unwrap_or(&1);
+            for offset in 1..=matches {
+                if let Some(c) = card_copies.get_mut(idx + offset) {
+                    *c += num_copies;
+                }
+            }
+        }
+
+        card_copies.into_iter().sum()
+    }
+}
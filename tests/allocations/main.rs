@@ -0,0 +1,109 @@
+//! Counts heap allocations made while parsing, against the owned parsers the days
+//! used to have in `baseline`, to keep the borrowed parsers honest.
+//! This lives in its own test binary because it installs a global allocator.
+
+mod baseline;
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+use aoc_2023::{day_2, day_4, day_7, day_8, Solution};
+
+struct Counting;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+/// Allocations made by `f` on this thread.
+fn allocations<T>(f: impl FnOnce() -> T) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    let result = f();
+    let after = ALLOCATIONS.with(Cell::get);
+    drop(result);
+    after - before
+}
+
+const LINES: usize = 1000;
+
+fn node_name(idx: usize) -> String {
+    let letter = |n: usize| char::from(b'A' + u8::try_from(n % 26).unwrap());
+    [idx / 676, idx / 26, idx].into_iter().map(letter).collect()
+}
+
+/// A different hand for each `idx` below 13^5.
+fn hand(idx: usize) -> String {
+    const CARDS: &[u8; 13] = b"23456789TJQKA";
+    [idx / 28561, idx / 2197, idx / 169, idx / 13, idx]
+        .into_iter()
+        .map(|n| char::from(CARDS[n % 13]))
+        .collect()
+}
+
+#[test]
+fn test_borrowed_parsers_allocate_a_tenth_as_often() {
+    let day_2 = (1..=LINES)
+        .map(|id| format!("Game {id}: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green"))
+        .collect::<Vec<_>>();
+    let day_4 = (1..=LINES)
+        .map(|id| format!("Card {id}: 41 48 83 86 17 | 83 31  6 12 15  9 22 53"))
+        .collect::<Vec<_>>();
+    let day_7 = (0..LINES)
+        .map(|idx| format!("{} {}", hand(idx * 97), idx + 1))
+        .collect::<Vec<_>>();
+    let day_8 = ["LR".to_string(), String::new()]
+        .into_iter()
+        .chain((0..LINES).map(|idx| {
+            let next = node_name((idx + 1) % LINES);
+            format!("{} = ({next}, {next})", node_name(idx))
+        }))
+        .collect::<Vec<_>>();
+
+    // Day 7 used to allocate while ranking the hands rather than while parsing them,
+    // so it is measured through to the first answer.
+    let (input_2, input_4, input_7, input_8) =
+        (day_2.clone(), day_4.clone(), day_7.clone(), day_8.clone());
+    for (day, borrowed, owned) in [
+        (
+            2,
+            allocations(|| day_2::Solver::try_new(input_2).unwrap()),
+            allocations(|| baseline::day_2::Solver::new(day_2)),
+        ),
+        (
+            4,
+            allocations(|| day_4::Solver::try_new(input_4).unwrap()),
+            allocations(|| baseline::day_4::Solver::new(day_4)),
+        ),
+        (
+            7,
+            allocations(|| day_7::Solver::try_new(input_7).unwrap().solve_first()),
+            allocations(|| baseline::day_7::Solver::new(day_7).solve_first()),
+        ),
+        (
+            8,
+            allocations(|| day_8::Solver::try_new(input_8).unwrap()),
+            allocations(|| baseline::day_8::Solver::new(day_8)),
+        ),
+    ] {
+        assert!(
+            borrowed * 10 <= owned,
+            "day {day} made {borrowed} allocations for {LINES} lines, against {owned} before"
+        );
+    }
+}
@@ -0,0 +1,467 @@
+//! Checks the solvers whose shortcuts are hard to trust against the naive ones in
+//! `reference`, on small random inputs. A disagreement is shrunk to the smallest
+//! input that still shows it before it's reported.
+
+mod reference;
+
+use std::{fmt::Write, panic};
+
+use aoc_2023::{day_5, day_6, day_7, day_8, Solution};
+use itertools::Itertools;
+
+/// How many random inputs each day is checked on.
+const CASES: u64 = 200;
+
+/// A small seedable generator (splitmix64), so a failing seed can be replayed.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `low..high`.
+    fn range(&mut self, low: u64, high: u64) -> u64 {
+        low + self.next() % (high - low)
+    }
+
+    fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.next() as usize % items.len()]
+    }
+}
+
+/// A puzzle input small enough to solve naively.
+trait Case: Clone {
+    fn generate(rng: &mut Rng) -> Self;
+
+    fn render(&self) -> String;
+
+    /// Valid inputs a step simpler than this one.
+    fn shrink(&self) -> Vec<Self>;
+
+    /// Both answers, from the reference solver.
+    fn expected(&self) -> (u64, u64);
+}
+
+type Fast = fn(&str) -> (u64, u64);
+
+fn fast<S: Solution<First = u64, Second = u64>>(text: &str) -> (u64, u64) {
+    let solver = S::try_from_str(text).unwrap();
+    (solver.solve_first(), solver.solve_second())
+}
+
+/// The fast answers, if they disagree with the reference ones. A panic counts as
+/// disagreeing.
+fn disagreement<C: Case>(case: &C, solve: Fast) -> Option<Option<(u64, u64)>> {
+    let text = case.render();
+    let answers = panic::catch_unwind(|| solve(&text)).ok();
+    (answers != Some(case.expected())).then_some(answers)
+}
+
+/// The first generated case the fast solver gets wrong, with its seed, shrunk for as
+/// long as the smaller case is still wrong.
+fn find_failure<C: Case>(solve: Fast) -> Option<(u64, C)> {
+    let (seed, mut case) = (0..CASES)
+        .map(|seed| (seed, C::generate(&mut Rng(seed))))
+        .find(|(_, case)| disagreement(case, solve).is_some())?;
+
+    while let Some(smaller) = case
+        .shrink()
+        .into_iter()
+        .find(|smaller| disagreement(smaller, solve).is_some())
+    {
+        case = smaller;
+    }
+
+    Some((seed, case))
+}
+
+fn check<C: Case>(solve: Fast) {
+    if let Some((seed, case)) = find_failure::<C>(solve) {
+        let answers = disagreement(&case, solve).unwrap();
+        panic!(
+            "seed {seed}, shrunk to:\n{}\nfast answers {answers:?}, reference answers {:?}",
+            case.render(),
+            case.expected()
+        );
+    }
+}
+
+/// Every copy of `items` with one item left out, keeping at least `min`.
+fn without_one<T: Clone>(items: &[T], min: usize) -> Vec<Vec<T>> {
+    if items.len() <= min {
+        return Vec::new();
+    }
+    (0..items.len())
+        .map(|idx| [&items[..idx], &items[idx + 1..]].concat())
+        .collect()
+}
+
+#[derive(Clone, Debug)]
+struct Almanac {
+    /// Pairs of range start and length.
+    seeds: Vec<(u64, u64)>,
+    layers: Vec<Vec<reference::Mapping>>,
+}
+
+impl Case for Almanac {
+    fn generate(rng: &mut Rng) -> Self {
+        let seeds = (0..rng.range(1, 4))
+            .map(|_| (rng.range(0, 100), rng.range(1, 20)))
+            .collect();
+        let layers = (0..rng.range(1, 5))
+            .map(|_| {
+                (0..rng.range(1, 5))
+                    .map(|_| (rng.range(0, 100), rng.range(0, 100), rng.range(1, 30)))
+                    .collect()
+            })
+            .collect();
+
+        Self { seeds, layers }
+    }
+
+    fn render(&self) -> String {
+        const LABELS: [&str; 8] = [
+            "seed",
+            "soil",
+            "fertilizer",
+            "water",
+            "light",
+            "temperature",
+            "humidity",
+            "location",
+        ];
+
+        let mut text = format!(
+            "seeds: {}\n",
+            self.seeds
+                .iter()
+                .map(|(start, len)| format!("{start} {len}"))
+                .join(" ")
+        );
+        for (idx, mappings) in self.layers.iter().enumerate() {
+            let (from, to) = (LABELS[idx % 7], LABELS[idx % 7 + 1]);
+            write!(text, "\n{from}-to-{to} map:\n").unwrap();
+            for (destination, source, range) in mappings {
+                writeln!(text, "{destination} {source} {range}").unwrap();
+            }
+        }
+        text
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        let mut smaller = Vec::new();
+        for seeds in without_one(&self.seeds, 1) {
+            smaller.push(Self {
+                seeds,
+                ..self.clone()
+            });
+        }
+        for layers in without_one(&self.layers, 1) {
+            smaller.push(Self {
+                layers,
+                ..self.clone()
+            });
+        }
+        for (idx, mappings) in self.layers.iter().enumerate() {
+            for mappings in without_one(mappings, 1) {
+                let mut layers = self.layers.clone();
+                layers[idx] = mappings;
+                smaller.push(Self {
+                    layers,
+                    ..self.clone()
+                });
+            }
+        }
+        for (idx, &(start, len)) in self.seeds.iter().enumerate() {
+            if len > 1 {
+                let mut seeds = self.seeds.clone();
+                seeds[idx] = (start, len / 2);
+                smaller.push(Self {
+                    seeds,
+                    ..self.clone()
+                });
+            }
+        }
+        smaller
+    }
+
+    fn expected(&self) -> (u64, u64) {
+        let seeds = self.seeds.iter().flat_map(|&(start, len)| [start, len]);
+        reference::almanac(&seeds.collect_vec(), &self.layers)
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Races {
+    /// Pairs of time and record distance.
+    races: Vec<(u64, u64)>,
+}
+
+impl Races {
+    /// Every race, and the long race read from the same sheet, can be won.
+    fn is_valid(&self) -> bool {
+        if self.races.is_empty() {
+            return false;
+        }
+        let concatenate = |numbers: Vec<u64>| numbers.iter().join("").parse::<u64>().unwrap();
+        let (times, distances): (Vec<_>, Vec<_>) = self.races.iter().copied().unzip();
+        let (time, distance) = (concatenate(times), concatenate(distances));
+
+        self.races
+            .iter()
+            .all(|&(time, distance)| 4 * distance < time * time)
+            && 4 * distance < time * time
+    }
+}
+
+impl Case for Races {
+    fn generate(rng: &mut Rng) -> Self {
+        loop {
+            let races = (0..rng.range(1, 4))
+                .map(|_| {
+                    let time = rng.range(1, 60);
+                    (time, rng.range(0, (time * time - 1) / 4 + 1))
+                })
+                .collect();
+            let races = Self { races };
+            if races.is_valid() {
+                return races;
+            }
+        }
+    }
+
+    fn render(&self) -> String {
+        let (times, distances): (Vec<_>, Vec<_>) = self.races.iter().copied().unzip();
+        format!(
+            "Time: {}\nDistance: {}",
+            times.iter().join(" "),
+            distances.iter().join(" ")
+        )
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        let mut smaller = without_one(&self.races, 1)
+            .into_iter()
+            .map(|races| Self { races })
+            .collect_vec();
+        for (idx, &(time, distance)) in self.races.iter().enumerate() {
+            let shorter = (distance > 0).then_some((time, distance / 2));
+            for race in shorter.into_iter().chain([(time - 1, distance)]) {
+                let mut races = self.races.clone();
+                races[idx] = race;
+                smaller.push(Self { races });
+            }
+        }
+        smaller.retain(Self::is_valid);
+        smaller
+    }
+
+    fn expected(&self) -> (u64, u64) {
+        let (times, distances): (Vec<_>, Vec<_>) = self.races.iter().copied().unzip();
+        reference::races(&times, &distances)
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Hands {
+    hands: Vec<(String, u64)>,
+}
+
+impl Case for Hands {
+    fn generate(rng: &mut Rng) -> Self {
+        // Drawing from only a few kinds of card makes pairs, jokers and ties common.
+        let kinds = "23456789TJQKA"
+            .chars()
+            .collect_vec()
+            .into_iter()
+            .filter(|_| rng.range(0, 3) == 0)
+            .chain(['J'])
+            .collect_vec();
+        let hands = (0..rng.range(1, 15))
+            .map(|_| {
+                let cards = (0..5).map(|_| rng.pick(&kinds)).collect();
+                (cards, rng.range(1, 1000))
+            })
+            .collect();
+
+        Self { hands }
+    }
+
+    fn render(&self) -> String {
+        self.hands
+            .iter()
+            .map(|(cards, bid)| format!("{cards} {bid}"))
+            .join("\n")
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        let mut smaller = without_one(&self.hands, 1)
+            .into_iter()
+            .map(|hands| Self { hands })
+            .collect_vec();
+        for (idx, (cards, bid)) in self.hands.iter().enumerate() {
+            if *bid > 1 {
+                let mut hands = self.hands.clone();
+                hands[idx].1 = 1;
+                smaller.push(Self { hands });
+            }
+            for (position, _) in cards.char_indices().filter(|&(_, card)| card != '2') {
+                let mut hands = self.hands.clone();
+                hands[idx].0.replace_range(position..=position, "2");
+                smaller.push(Self { hands });
+            }
+        }
+        smaller
+    }
+
+    fn expected(&self) -> (u64, u64) {
+        reference::camel_cards(&self.hands)
+    }
+}
+
+/// A day 8 network with the shape the puzzle promises: every ghost reaches its `Z`
+/// node after a whole number of passes through the directions, and then loops back
+/// to it in exactly as many steps.
+#[derive(Clone, Debug)]
+struct Network {
+    order: String,
+    /// How many passes through the directions each ghost's loop takes. The first
+    /// ghost walks from `AAA` to `ZZZ`.
+    passes: Vec<usize>,
+}
+
+impl Network {
+    fn nodes(&self) -> Vec<(String, String, String)> {
+        let mut nodes = Vec::new();
+        for (ghost, &passes) in self.passes.iter().enumerate() {
+            let (start, end) = if ghost == 0 {
+                ("AAA".to_string(), "ZZZ".to_string())
+            } else {
+                (format!("{ghost}GA"), format!("{ghost}GZ"))
+            };
+            let len = passes * self.order.len();
+            // The ghost's path: start, then len - 1 plain nodes, then its end.
+            let path = std::iter::once(start.clone())
+                .chain((1..len).map(|step| format!("N{ghost}S{step}")))
+                .chain([end])
+                .collect_vec();
+
+            // From the end, the loop carries on where the start left off.
+            for (step, name) in path.iter().enumerate() {
+                let next = &path[if step == len { 1 } else { step + 1 }];
+                let direction = self.order.as_bytes()[step % self.order.len()];
+                let (left, right) = if direction == b'L' {
+                    (next.clone(), start.clone())
+                } else {
+                    (start.clone(), next.clone())
+                };
+                nodes.push((name.clone(), left, right));
+            }
+        }
+        nodes
+    }
+}
+
+impl Case for Network {
+    fn generate(rng: &mut Rng) -> Self {
+        let order = (0..rng.range(1, 4))
+            .map(|_| rng.pick(&['L', 'R']))
+            .collect();
+        let passes = (0..rng.range(1, 4))
+            .map(|_| rng.range(1, 5) as usize)
+            .collect();
+
+        Self { order, passes }
+    }
+
+    fn render(&self) -> String {
+        let nodes = self
+            .nodes()
+            .into_iter()
+            .map(|(name, left, right)| format!("{name} = ({left}, {right})"))
+            .join("\n");
+        format!("{}\n\n{nodes}", self.order)
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        let mut smaller = Vec::new();
+        for idx in 1..self.passes.len() {
+            let mut passes = self.passes.clone();
+            passes.remove(idx);
+            smaller.push(Self {
+                passes,
+                ..self.clone()
+            });
+        }
+        for (idx, &passes) in self.passes.iter().enumerate() {
+            if passes > 1 {
+                let mut shorter = self.passes.clone();
+                shorter[idx] = 1;
+                smaller.push(Self {
+                    passes: shorter,
+                    ..self.clone()
+                });
+            }
+        }
+        if self.order.len() > 1 {
+            smaller.push(Self {
+                order: self.order[..1].to_string(),
+                ..self.clone()
+            });
+        }
+        smaller
+    }
+
+    fn expected(&self) -> (u64, u64) {
+        reference::network(&self.order, &self.nodes())
+    }
+}
+
+#[test]
+fn test_day_5_matches_reference() {
+    check::<Almanac>(fast::<day_5::Solver>);
+}
+
+#[test]
+fn test_day_6_matches_reference() {
+    check::<Races>(fast::<day_6::Solver>);
+}
+
+#[test]
+fn test_day_7_matches_reference() {
+    check::<Hands>(fast::<day_7::Solver>);
+}
+
+#[test]
+fn test_day_8_matches_reference() {
+    check::<Network>(fast::<day_8::Solver>);
+}
+
+#[test]
+fn test_failures_are_shrunk() {
+    // Wrong whenever any race is longer than 30.
+    let buggy: Fast = |text| {
+        let answers = fast::<day_6::Solver>(text);
+        let long = text
+            .lines()
+            .next()
+            .unwrap()
+            .split_whitespace()
+            .skip(1)
+            .any(|time| time.parse::<u64>().unwrap() > 30);
+        if long {
+            (answers.0 + 1, answers.1)
+        } else {
+            answers
+        }
+    };
+
+    let (_, races) = find_failure::<Races>(buggy).unwrap();
+    assert_eq!(1, races.races.len());
+    assert_eq!((31, 0), races.races[0]);
+}
@@ -0,0 +1,152 @@
+//! Deliberately naive solvers, written to be obviously right rather than fast. They
+//! take the puzzle data directly, so they share no parsing with the real solvers.
+
+use std::collections::HashMap;
+
+use itertools::Itertools;
+
+/// One `destination source range` line of a day 5 map.
+pub type Mapping = (u64, u64, u64);
+
+/// Day 5: follow every seed through every map, one number at a time.
+pub fn almanac(seeds: &[u64], layers: &[Vec<Mapping>]) -> (u64, u64) {
+    let location = |seed: u64| {
+        layers.iter().fold(seed, |value, mappings| {
+            mappings
+                .iter()
+                .find(|&&(_, source, range)| source <= value && value < source + range)
+                .map_or(value, |&(destination, source, _)| {
+                    destination + value - source
+                })
+        })
+    };
+
+    let first = seeds.iter().map(|&seed| location(seed)).min().unwrap();
+    let second = seeds
+        .iter()
+        .tuples()
+        .flat_map(|(&start, &len)| start..start + len)
+        .map(location)
+        .min()
+        .unwrap();
+
+    (first, second)
+}
+
+/// Day 6: try every way to split each race between holding and moving.
+pub fn races(times: &[u64], distances: &[u64]) -> (u64, u64) {
+    let ways = |time: u64, distance: u64| {
+        (0..=time)
+            .filter(|hold| hold * (time - hold) > distance)
+            .count() as u64
+    };
+    let concatenate = |numbers: &[u64]| numbers.iter().join("").parse().unwrap();
+
+    let first = times
+        .iter()
+        .zip(distances)
+        .map(|(&time, &distance)| ways(time, distance))
+        .product();
+    let second = ways(concatenate(times), concatenate(distances));
+
+    (first, second)
+}
+
+/// Day 7: rank the hands by sorting them, trying every card a joker could stand for.
+pub fn camel_cards(hands: &[(String, u64)]) -> (u64, u64) {
+    (
+        winnings(hands, "23456789TJQKA", strength),
+        winnings(hands, "J23456789TQKA", |cards| {
+            let jokers = cards.iter().filter(|&&c| c == 'J').count();
+            let others = cards.iter().copied().filter(|&c| c != 'J').collect_vec();
+            "23456789TQKA"
+                .chars()
+                .combinations_with_replacement(jokers)
+                .map(|stand_ins| strength(&[others.clone(), stand_ins].concat()))
+                .max()
+                .unwrap()
+        }),
+    )
+}
+
+/// The hand type, from high card as 0 to five of a kind as 6.
+fn strength(cards: &[char]) -> u8 {
+    let counts = cards
+        .iter()
+        .counts()
+        .into_values()
+        .sorted()
+        .rev()
+        .collect_vec();
+
+    match counts[..] {
+        [5] => 6,
+        [4, 1] => 5,
+        [3, 2] => 4,
+        [3, 1, 1] => 3,
+        [2, 2, 1] => 2,
+        [2, 1, 1, 1] => 1,
+        _ => 0,
+    }
+}
+
+fn winnings(hands: &[(String, u64)], order: &str, strength: impl Fn(&[char]) -> u8) -> u64 {
+    let key = |cards: &str| {
+        let cards = cards.chars().collect_vec();
+        let values = cards.iter().map(|&c| order.find(c).unwrap()).collect_vec();
+        (strength(&cards), values)
+    };
+
+    // The sort is stable, so identical hands keep their input order.
+    hands
+        .iter()
+        .sorted_by_cached_key(|(cards, _)| key(cards))
+        .zip(1..)
+        .map(|((_, bid), rank)| rank * bid)
+        .sum()
+}
+
+/// Day 8: walk from `AAA`, and then walk every ghost in step until all of them stand
+/// on a node ending in `Z` together.
+pub fn network(order: &str, nodes: &[(String, String, String)]) -> (u64, u64) {
+    let edges = nodes
+        .iter()
+        .map(|(name, left, right)| (name.as_str(), (left.as_str(), right.as_str())))
+        .collect::<HashMap<_, _>>();
+    let step = |node: &str, direction: char| {
+        let (left, right) = edges[node];
+        if direction == 'L' {
+            left
+        } else {
+            right
+        }
+    };
+
+    let mut node = "AAA";
+    let mut first = 0;
+    for direction in order.chars().cycle() {
+        if node == "ZZZ" && first > 0 {
+            break;
+        }
+        node = step(node, direction);
+        first += 1;
+    }
+
+    let mut ghosts = nodes
+        .iter()
+        .map(|(name, _, _)| name.as_str())
+        .filter(|name| name.ends_with('A'))
+        .collect_vec();
+    let mut second = 0;
+    for direction in order.chars().cycle() {
+        if second > 0 && ghosts.iter().all(|ghost| ghost.ends_with('Z')) {
+            break;
+        }
+        for ghost in &mut ghosts {
+            *ghost = step(ghost, direction);
+        }
+        second += 1;
+    }
+
+    (first, second)
+}